@@ -0,0 +1,163 @@
+use crate::config;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Serializes the read-modify-write cycles on audit_log.json; Docker commands
+// record concurrently
+static AUDIT_LOG_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditLogEntry {
+    pub timestamp: i64,
+    pub action: String,
+    pub target: String,
+    pub success: bool,
+}
+
+fn get_audit_log_file() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("signalforge-dev")
+        .join("audit_log.json")
+}
+
+fn load_audit_log() -> Result<Vec<AuditLogEntry>, String> {
+    let path = get_audit_log_file();
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read audit log: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse audit log: {}", e))
+}
+
+fn save_audit_log(entries: &[AuditLogEntry]) -> Result<(), String> {
+    let path = get_audit_log_file();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize audit log: {}", e))?;
+
+    fs::write(&path, content)
+        .map_err(|e| format!("Failed to write audit log: {}", e))?;
+
+    Ok(())
+}
+
+// Oldest timestamp still inside the retention window
+fn retention_cutoff(retention_days: u32) -> i64 {
+    Utc::now().timestamp() - retention_days as i64 * 86400
+}
+
+// Drops expired entries, then keeps only the newest `max_entries`
+pub fn apply_retention(entries: &mut Vec<AuditLogEntry>, retention_days: u32, max_entries: u32) {
+    let cutoff = retention_cutoff(retention_days);
+    entries.retain(|e| e.timestamp >= cutoff);
+    entries.sort_by_key(|e| e.timestamp);
+
+    let max_entries = max_entries as usize;
+    if entries.len() > max_entries {
+        entries.drain(..entries.len() - max_entries);
+    }
+}
+
+pub fn record(action: &str, target: &str, success: bool) {
+    let _guard = AUDIT_LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let Ok(mut entries) = load_audit_log() else {
+        return;
+    };
+
+    entries.push(AuditLogEntry {
+        timestamp: Utc::now().timestamp(),
+        action: action.to_string(),
+        target: target.to_string(),
+        success,
+    });
+
+    let app_config = config::load_app_config().unwrap_or_default();
+    apply_retention(&mut entries, app_config.log_retention_days, app_config.max_log_entries);
+
+    let _ = save_audit_log(&entries);
+}
+
+pub fn prune_audit_log() -> Result<(), String> {
+    let app_config = config::load_app_config()?;
+    let _guard = AUDIT_LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load_audit_log()?;
+    let before = entries.len();
+
+    apply_retention(&mut entries, app_config.log_retention_days, app_config.max_log_entries);
+
+    if entries.len() != before {
+        save_audit_log(&entries)?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_audit_log() -> Result<Vec<AuditLogEntry>, String> {
+    let app_config = config::load_app_config()?;
+    let cutoff = retention_cutoff(app_config.log_retention_days);
+    let _guard = AUDIT_LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    Ok(load_audit_log()?
+        .into_iter()
+        .filter(|e| e.timestamp >= cutoff)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: i64) -> AuditLogEntry {
+        AuditLogEntry {
+            timestamp,
+            action: "start_container".to_string(),
+            target: "signalforge-nginx".to_string(),
+            success: true,
+        }
+    }
+
+    #[test]
+    fn apply_retention_enforces_age_and_count() {
+        let now = Utc::now().timestamp();
+        // 1500 entries, one every hour going back ~62 days, in shuffled order
+        let mut entries: Vec<AuditLogEntry> = (0..1500i64)
+            .map(|i| entry(now - ((i * 7) % 1500) * 3600))
+            .collect();
+
+        apply_retention(&mut entries, 30, 500);
+
+        assert_eq!(entries.len(), 500);
+        let cutoff = now - 30 * 86400;
+        assert!(entries.iter().all(|e| e.timestamp >= cutoff));
+        assert!(entries.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+        // The newest entries are the ones kept
+        assert_eq!(entries.last().unwrap().timestamp, now);
+        assert_eq!(entries[0].timestamp, now - 499 * 3600);
+    }
+
+    #[test]
+    fn apply_retention_drops_expired_entries_below_the_count_limit() {
+        let now = Utc::now().timestamp();
+        // Half an hour off the hour so none sits exactly on the cutoff
+        let mut entries: Vec<AuditLogEntry> = (0..1500i64).map(|i| entry(now - i * 3600 - 1800)).collect();
+
+        apply_retention(&mut entries, 7, 5000);
+
+        assert_eq!(entries.len(), 7 * 24);
+    }
+}
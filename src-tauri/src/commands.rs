@@ -1,18 +1,186 @@
-use crate::docker::{ContainerInfo, ContainerStats, DockerClient, DockerInfo, NetworkTopology};
+use crate::audit;
+use crate::compose::{self, ComposeServiceStatus, ProjectStats};
+use crate::config::{self, RemoteDockerConfig};
+use crate::filesystem::DirectoryCache;
+use crate::watcher::FileWatchers;
+use crate::docker::{
+    self, ContainerInfo, ContainerLabels, ContainerNetworkInterface, ContainerStats,
+    CopyFromResult, CopyToResult, DockerClient, DockerContext, DockerDiskUsage, DockerImage, DockerInfo,
+    DockerNetwork, DockerVolume, ExecResult, NetworkTopology, PruneResult, ResourceLimits,
+};
+use bollard::auth::DockerCredentials;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tauri::State;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+// How often the background monitor refreshes registered projects
+const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+// Docker samples stats about once a second, so faster intervals gain nothing
+const MIN_STATS_INTERVAL_MS: u64 = 1000;
+
+// How long exec_container waits when the caller gives no timeout
+const DEFAULT_EXEC_TIMEOUT_SECS: u64 = 300;
+
+// Largest file upload_file_to_container will accept (100MB)
+const MAX_UPLOAD_BYTES: u64 = 100 * 1024 * 1024;
+
+// Last known service statuses for each monitored project, keyed by project id
+pub type ProjectServiceRegistry = HashMap<String, Option<(Instant, Vec<ComposeServiceStatus>)>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InitResult {
+    pub docker_connected: bool,
+    pub config_valid: bool,
+    pub directories_ready: bool,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CachedStatus {
+    pub project_id: String,
+    pub services: Vec<ComposeServiceStatus>,
+    pub age_seconds: u64,
+}
 
 pub struct AppState {
     pub docker: Arc<Mutex<Option<DockerClient>>>,
+    pub project_stats_cache: Arc<Mutex<HashMap<String, (Instant, ProjectStats)>>>,
+    pub file_watchers: Arc<Mutex<FileWatchers>>,
+    pub authenticated_registries: Arc<Mutex<Vec<String>>>,
+    pub project_service_registry: Arc<Mutex<ProjectServiceRegistry>>,
+    pub background_tasks_started: Arc<AtomicBool>,
+    pub directory_cache: Arc<Mutex<DirectoryCache>>,
+    pub cache_hit_count: Arc<AtomicU64>,
+    pub stats_subscriptions: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+    pub log_subscriptions: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+    pub compose_log_processes: Arc<Mutex<HashMap<String, tokio::process::Child>>>,
 }
 
 impl AppState {
     pub fn new() -> Self {
-        let docker = DockerClient::new().ok();
+        let docker = connect_configured_docker().ok();
         Self {
             docker: Arc::new(Mutex::new(docker)),
+            project_stats_cache: Arc::new(Mutex::new(HashMap::new())),
+            file_watchers: Arc::new(Mutex::new(HashMap::new())),
+            authenticated_registries: Arc::new(Mutex::new(Vec::new())),
+            project_service_registry: Arc::new(Mutex::new(HashMap::new())),
+            background_tasks_started: Arc::new(AtomicBool::new(false)),
+            directory_cache: Arc::new(Mutex::new(HashMap::new())),
+            cache_hit_count: Arc::new(AtomicU64::new(0)),
+            stats_subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            log_subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            compose_log_processes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+// Reconnects to the remote host saved by connect_docker_remote or the context
+// chosen with use_docker_context, if any, otherwise to the local daemon
+fn connect_configured_docker() -> Result<DockerClient, String> {
+    let app_config = config::load_app_config().ok();
+
+    match app_config {
+        Some(config::AppConfig { remote_docker: Some(remote), .. }) => DockerClient::connect_remote(
+            &remote.host,
+            remote.ca_path.as_deref().map(Path::new),
+            remote.cert_path.as_deref().map(Path::new),
+            remote.key_path.as_deref().map(Path::new),
+        ),
+        Some(config::AppConfig { preferred_context: Some(context), .. }) => {
+            DockerClient::from_context(&context)
+        }
+        _ => DockerClient::new(),
+    }
+}
+
+// Brings subsystems up in dependency order: config first, then the
+// directories it names, then Docker and the background tasks that use it
+#[tauri::command]
+pub async fn initialize_app(app: AppHandle, state: State<'_, AppState>) -> Result<InitResult, String> {
+    let mut result = prepare_subsystems(&state.docker, connect_configured_docker).await;
+
+    // The frontend may call this again after a reload; only start tasks once
+    if !state.background_tasks_started.swap(true, Ordering::SeqCst) {
+        spawn_status_monitor(app);
+
+        if let Err(e) = audit::prune_audit_log() {
+            result.warnings.push(e);
+        }
+    }
+
+    Ok(result)
+}
+
+// Config, directories and the Docker client, reporting what came up. Docker
+// is only connected when there is no client yet.
+async fn prepare_subsystems(
+    docker: &Mutex<Option<DockerClient>>,
+    connect: impl FnOnce() -> Result<DockerClient, String>,
+) -> InitResult {
+    let mut warnings = Vec::new();
+
+    match config::migrate_config_file().await {
+        Ok(result) if result.migrated => warnings.push(format!(
+            "Config migrated, added fields: {}",
+            result.fields_added.join(", ")
+        )),
+        Ok(_) => {}
+        Err(e) => warnings.push(e),
+    }
+
+    let app_config = match config::load_app_config() {
+        Ok(app_config) => Some(app_config),
+        Err(e) => {
+            warnings.push(e);
+            None
+        }
+    };
+
+    let directories_ready = match &app_config {
+        Some(_) => match config::ensure_directories().await {
+            Ok(()) => true,
+            Err(e) => {
+                warnings.push(e);
+                false
+            }
+        },
+        None => false,
+    };
+
+    let config_valid = match &app_config {
+        Some(app_config) => {
+            let problems = config::validate_app_config(app_config);
+            let valid = problems.is_empty();
+            warnings.extend(problems);
+            valid
+        }
+        None => false,
+    };
+
+    let docker_connected = {
+        let mut docker = docker.lock().await;
+        if docker.is_none() {
+            match connect() {
+                Ok(client) => *docker = Some(client),
+                Err(e) => warnings.push(e),
+            }
         }
+        docker.is_some()
+    };
+
+    InitResult {
+        docker_connected,
+        config_valid,
+        directories_ready,
+        warnings,
     }
 }
 
@@ -35,38 +203,117 @@ pub async fn connect_docker(state: State<'_, AppState>) -> Result<bool, String>
 }
 
 #[tauri::command]
-pub async fn list_containers(state: State<'_, AppState>) -> Result<Vec<ContainerInfo>, String> {
+pub async fn list_containers(
+    filter_labels: Option<HashMap<String, String>>,
+    include_limits: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ContainerInfo>, String> {
     let docker = state.docker.lock().await;
     match docker.as_ref() {
-        Some(client) => client.list_containers().await,
+        Some(client) => client.list_containers(filter_labels, include_limits.unwrap_or(false)).await,
         None => Err("Docker is not connected".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn start_container(id: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn list_all_containers(
+    include_non_signalforge: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<ContainerInfo>, String> {
     let docker = state.docker.lock().await;
     match docker.as_ref() {
-        Some(client) => client.start_container(&id).await,
+        Some(client) => client.list_all_containers(include_non_signalforge).await,
         None => Err("Docker is not connected".to_string()),
     }
 }
 
+#[tauri::command]
+pub async fn start_container(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.start_container(&id).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("start_container", &id, result.is_ok());
+    result
+}
+
 #[tauri::command]
 pub async fn stop_container(id: String, state: State<'_, AppState>) -> Result<(), String> {
     let docker = state.docker.lock().await;
-    match docker.as_ref() {
+    let result = match docker.as_ref() {
         Some(client) => client.stop_container(&id).await,
         None => Err("Docker is not connected".to_string()),
-    }
+    };
+    audit::record("stop_container", &id, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn kill_container(id: String, signal: String, state: State<'_, AppState>) -> Result<(), String> {
+    // Validate before touching Docker so bad input never reaches the daemon
+    let signal = docker::normalize_signal(&signal)?;
+
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.kill_container(&id, &signal).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("kill_container", &format!("{} ({})", id, signal), result.is_ok());
+    result
 }
 
 #[tauri::command]
 pub async fn restart_container(id: String, state: State<'_, AppState>) -> Result<(), String> {
     let docker = state.docker.lock().await;
-    match docker.as_ref() {
+    let result = match docker.as_ref() {
         Some(client) => client.restart_container(&id).await,
         None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("restart_container", &id, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn update_container_resources(
+    id: String,
+    memory_bytes: Option<u64>,
+    cpu_quota: Option<i64>,
+    cpu_period: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => {
+            client
+                .update_container_resources(&id, memory_bytes, cpu_quota, cpu_period)
+                .await
+        }
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+// Same as update_container_resources, under the name the container view uses
+#[tauri::command]
+pub async fn set_container_resources(
+    id: String,
+    memory_bytes: Option<u64>,
+    cpu_quota: Option<i64>,
+    cpu_period: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    update_container_resources(id, memory_bytes, cpu_quota, cpu_period, state).await
+}
+
+#[tauri::command]
+pub async fn get_container_resource_limits(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<ResourceLimits, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.get_container_resource_limits(id).await,
+        None => Err("Docker is not connected".to_string()),
     }
 }
 
@@ -82,19 +329,331 @@ pub async fn get_container_stats(
     }
 }
 
+// Adds a streaming task under `id`, aborting the one it replaces. Streams
+// that ended on their own (container stopped or removed) are pruned here so
+// the map doesn't keep growing with dead ids.
+fn insert_subscription(subscriptions: &mut HashMap<String, JoinHandle<()>>, id: String, handle: JoinHandle<()>) {
+    subscriptions.retain(|_, task| !task.is_finished());
+
+    if let Some(previous) = subscriptions.insert(id, handle) {
+        previous.abort();
+    }
+}
+
+#[tauri::command]
+pub async fn subscribe_container_stats(
+    id: String,
+    interval_ms: u64,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let client = state
+        .docker
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Docker is not connected".to_string())?;
+
+    let event = format!("container-stats:{}", id);
+    let interval = Duration::from_millis(interval_ms.max(MIN_STATS_INTERVAL_MS));
+    let container_id = id.clone();
+
+    let handle = tokio::spawn(async move {
+        let result = client
+            .stream_container_stats(&container_id, interval, |stats| {
+                let _ = app.emit(&event, stats);
+            })
+            .await;
+
+        if let Err(e) = result {
+            let _ = app.emit(&format!("container-stats-error:{}", container_id), e);
+        }
+    });
+
+    // Resubscribing replaces the previous stream for the same container
+    insert_subscription(&mut *state.stats_subscriptions.lock().await, id, handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unsubscribe_container_stats(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(handle) = state.stats_subscriptions.lock().await.remove(&id) {
+        handle.abort();
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_container_logs(
     id: String,
     tail: Option<u64>,
+    since: Option<i64>,
+    until: Option<i64>,
     state: State<'_, AppState>,
 ) -> Result<Vec<String>, String> {
     let docker = state.docker.lock().await;
     match docker.as_ref() {
-        Some(client) => client.get_container_logs(&id, tail).await,
+        Some(client) => client.get_container_logs(&id, tail, since, until).await,
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn tail_container_logs(
+    id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let client = state
+        .docker
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Docker is not connected".to_string())?;
+
+    let event = format!("container-log:{}", id);
+    let container_id = id.clone();
+
+    let handle = tokio::spawn(async move {
+        let result = client
+            .follow_container_logs(&container_id, |line| {
+                let _ = app.emit(&event, line);
+            })
+            .await;
+
+        // Tell the frontend the stream is over rather than letting it go quiet
+        let _ = app.emit(&format!("container-log-end:{}", container_id), result.err());
+    });
+
+    insert_subscription(&mut *state.log_subscriptions.lock().await, id, handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn untail_container_logs(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(handle) = state.log_subscriptions.lock().await.remove(&id) {
+        handle.abort();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn copy_from_container(
+    id: String,
+    container_path: String,
+    host_dest_dir: String,
+    state: State<'_, AppState>,
+) -> Result<CopyFromResult, String> {
+    let client = state
+        .docker
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Docker is not connected".to_string())?;
+
+    client
+        .copy_from_container(&id, &container_path, &host_dest_dir)
+        .await
+}
+
+#[tauri::command]
+pub async fn copy_to_container(
+    id: String,
+    host_path: String,
+    container_dest_dir: String,
+    state: State<'_, AppState>,
+) -> Result<CopyToResult, String> {
+    let client = state
+        .docker
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Docker is not connected".to_string())?;
+
+    let result = client.copy_to_container(&id, &host_path, &container_dest_dir).await;
+    audit::record("copy_to_container", &id, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn exec_container(
+    id: String,
+    cmd: Vec<String>,
+    env: Option<Vec<String>>,
+    timeout_secs: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<ExecResult, String> {
+    let client = state
+        .docker
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Docker is not connected".to_string())?;
+
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_EXEC_TIMEOUT_SECS));
+    let result = client.exec_container(&id, cmd, env, timeout).await;
+
+    audit::record("exec_container", &id, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn upload_file_to_container(
+    host_path: String,
+    container_id: String,
+    container_dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let metadata = std::fs::metadata(&host_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    if !metadata.is_file() {
+        return Err(format!("Not a file: {}", host_path));
+    }
+    if metadata.len() > MAX_UPLOAD_BYTES {
+        return Err(format!(
+            "File is too large to upload ({} bytes, limit is {} bytes)",
+            metadata.len(),
+            MAX_UPLOAD_BYTES
+        ));
+    }
+
+    // A trailing slash means "into this directory", keeping the host file name
+    let dest = std::path::Path::new(&container_dest_path);
+    let (container_dir, file_name) = if container_dest_path.ends_with('/') {
+        let name = std::path::Path::new(&host_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| format!("Invalid file path: {}", host_path))?;
+        (container_dest_path.clone(), name)
+    } else {
+        let name = dest
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| format!("Invalid destination path: {}", container_dest_path))?;
+        let dir = dest
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| "/".to_string());
+        (dir, name)
+    };
+
+    let contents = std::fs::read(&host_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let archive = docker::create_tar_archive(&file_name, &contents)?;
+
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.upload_to_container(&container_id, &container_dir, archive).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("upload_file_to_container", &container_id, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn list_networks(state: State<'_, AppState>) -> Result<Vec<DockerNetwork>, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.list_networks().await,
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn create_network(
+    name: String,
+    driver: Option<String>,
+    subnet: Option<String>,
+    gateway: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<DockerNetwork, String> {
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.create_network(&name, driver, subnet, gateway).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("create_network", &name, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn remove_network(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.remove_network(&id).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("remove_network", &id, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn list_volumes(state: State<'_, AppState>) -> Result<Vec<DockerVolume>, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.list_volumes().await,
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn inspect_volume(name: String, state: State<'_, AppState>) -> Result<DockerVolume, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.inspect_volume(&name).await,
         None => Err("Docker is not connected".to_string()),
     }
 }
 
+#[tauri::command]
+pub async fn create_volume(
+    name: String,
+    driver: Option<String>,
+    labels: Option<HashMap<String, String>>,
+    state: State<'_, AppState>,
+) -> Result<DockerVolume, String> {
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.create_volume(&name, driver, labels.unwrap_or_default()).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("create_volume", &name, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn remove_volume(name: String, force: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.remove_volume(&name, force).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("remove_volume", &name, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn backup_volume(
+    name: String,
+    dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let client = state
+        .docker
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Docker is not connected".to_string())?;
+
+    let result = client.backup_volume(&name, &dest_path).await;
+    audit::record("backup_volume", &name, result.is_ok());
+    result
+}
+
 #[tauri::command]
 pub async fn get_docker_info(state: State<'_, AppState>) -> Result<DockerInfo, String> {
     let docker = state.docker.lock().await;
@@ -104,6 +663,106 @@ pub async fn get_docker_info(state: State<'_, AppState>) -> Result<DockerInfo, S
     }
 }
 
+#[tauri::command]
+pub async fn get_docker_disk_usage(state: State<'_, AppState>) -> Result<DockerDiskUsage, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.get_disk_usage().await,
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn docker_system_prune(volumes: bool, state: State<'_, AppState>) -> Result<PruneResult, String> {
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.system_prune(volumes).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    let target = if volumes { "containers, images, networks, volumes" } else { "containers, images, networks" };
+    audit::record("docker_system_prune", target, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn tag_image(
+    source: String,
+    repository: String,
+    tag: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.tag_image(&source, &repository, &tag).await,
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn list_images(state: State<'_, AppState>) -> Result<Vec<DockerImage>, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.list_images().await,
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn pull_image(name: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    // Clone the client so other commands aren't locked out during the pull
+    let client = state
+        .docker
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Docker is not connected".to_string())?;
+
+    let credentials = config::find_registry_credential(&docker::image_registry_host(&name))?
+        .map(|credential| DockerCredentials {
+            username: Some(credential.username),
+            password: Some(credential.password),
+            // Docker Hub still authenticates against its legacy v1 address
+            serveraddress: Some(match credential.server.as_str() {
+                "docker.io" => "https://index.docker.io/v1/".to_string(),
+                _ => credential.server,
+            }),
+            ..Default::default()
+        });
+
+    let event = format!("image-pull-progress:{}", name);
+    let result = client
+        .pull_image(&name, credentials, |progress| {
+            let _ = app.emit(&event, progress);
+        })
+        .await;
+
+    audit::record("pull_image", &name, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn remove_image(id: String, force: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let docker = state.docker.lock().await;
+    let result = match docker.as_ref() {
+        Some(client) => client.remove_image(&id, force).await,
+        None => Err("Docker is not connected".to_string()),
+    };
+    audit::record("remove_image", &id, result.is_ok());
+    result
+}
+
+#[tauri::command]
+pub async fn list_image_tags(
+    image_name: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.list_image_tags(&image_name).await,
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn get_network_topology(state: State<'_, AppState>) -> Result<NetworkTopology, String> {
     let docker = state.docker.lock().await;
@@ -112,3 +771,327 @@ pub async fn get_network_topology(state: State<'_, AppState>) -> Result<NetworkT
         None => Err("Docker is not connected".to_string()),
     }
 }
+
+#[tauri::command]
+pub async fn get_container_labels(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<ContainerLabels, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.get_container_labels(id).await.map(ContainerLabels::from_labels),
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_container_network_settings(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ContainerNetworkInterface>, String> {
+    let docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.get_container_network_settings(&id).await,
+        None => Err("Docker is not connected".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn connect_docker_remote(
+    host: String,
+    ca_path: Option<String>,
+    cert_path: Option<String>,
+    key_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<DockerInfo, String> {
+    let client = DockerClient::connect_remote(
+        &host,
+        ca_path.as_deref().map(Path::new),
+        cert_path.as_deref().map(Path::new),
+        key_path.as_deref().map(Path::new),
+    )?;
+
+    // bollard connects lazily, so make a request before saving anything
+    let info = client.get_docker_info().await?;
+
+    *state.docker.lock().await = Some(client);
+
+    let mut app_config = config::get_app_config().await?;
+    app_config.remote_docker = Some(RemoteDockerConfig {
+        host,
+        ca_path,
+        cert_path,
+        key_path,
+    });
+    config::save_app_config(app_config).await?;
+
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn list_docker_contexts() -> Result<Vec<DockerContext>, String> {
+    DockerClient::list_contexts()
+}
+
+#[tauri::command]
+pub async fn use_docker_context(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    DockerClient::use_context(&name)?;
+
+    let mut docker = state.docker.lock().await;
+    match docker.as_ref() {
+        Some(client) => client.reconnect(&name).await?,
+        None => *docker = Some(DockerClient::from_context(&name)?),
+    }
+
+    let mut app_config = config::get_app_config().await?;
+    app_config.preferred_context = Some(name);
+    app_config.remote_docker = None;
+    config::save_app_config(app_config).await
+}
+
+#[tauri::command]
+pub async fn docker_login(
+    server: String,
+    username: String,
+    password: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let output = DockerClient::login(&server, &username, &password)?;
+
+    let mut registries = state.authenticated_registries.lock().await;
+    if !registries.contains(&server) {
+        registries.push(server);
+    }
+
+    Ok(output)
+}
+
+#[tauri::command]
+pub async fn docker_logout(server: String, state: State<'_, AppState>) -> Result<(), String> {
+    DockerClient::logout(&server)?;
+
+    state
+        .authenticated_registries
+        .lock()
+        .await
+        .retain(|r| r != &server);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_authenticated_registries(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    Ok(state.authenticated_registries.lock().await.clone())
+}
+
+// Stores a fresh snapshot and reports whether it differs from the last one
+async fn update_cached_status(
+    registry: &Mutex<ProjectServiceRegistry>,
+    project_id: &str,
+    services: Vec<ComposeServiceStatus>,
+) -> bool {
+    let mut registry = registry.lock().await;
+
+    // The project may have been unregistered while its status was fetched
+    let Some(entry) = registry.get_mut(project_id) else {
+        return false;
+    };
+
+    let changed = entry.as_ref().map(|(_, previous)| previous != &services).unwrap_or(true);
+    *entry = Some((Instant::now(), services));
+    changed
+}
+
+// Refreshes every monitored project on a fixed interval and emits
+// "project-status-updated" whenever a project's services change
+pub fn spawn_status_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let registry = app.state::<AppState>().project_service_registry.clone();
+        let mut interval = tokio::time::interval(STATUS_REFRESH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let project_ids: Vec<String> = registry.lock().await.keys().cloned().collect();
+
+            for project_id in project_ids {
+                let services = match compose::get_project_service_statuses(project_id.clone()).await {
+                    Ok(services) => services,
+                    Err(_) => continue,
+                };
+
+                if update_cached_status(&registry, &project_id, services.clone()).await {
+                    let _ = app.emit(
+                        "project-status-updated",
+                        CachedStatus {
+                            project_id,
+                            services,
+                            age_seconds: 0,
+                        },
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn register_project_for_monitoring(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    // Make sure the project exists before it is polled
+    compose::get_project(project_id.clone()).await?;
+
+    state
+        .project_service_registry
+        .lock()
+        .await
+        .entry(project_id.clone())
+        .or_insert(None);
+
+    let services = compose::get_project_service_statuses(project_id.clone()).await?;
+    update_cached_status(&state.project_service_registry, &project_id, services).await;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unregister_project_for_monitoring(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .project_service_registry
+        .lock()
+        .await
+        .remove(&project_id)
+        .map(|_| ())
+        .ok_or_else(|| format!("Project is not monitored: {}", project_id))
+}
+
+#[tauri::command]
+pub async fn get_cached_project_status(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<CachedStatus, String> {
+    let registry = state.project_service_registry.lock().await;
+
+    let (updated_at, services) = registry
+        .get(&project_id)
+        .ok_or_else(|| format!("Project is not monitored: {}", project_id))?
+        .as_ref()
+        .ok_or_else(|| format!("No status cached yet for project: {}", project_id))?;
+
+    Ok(CachedStatus {
+        project_id,
+        services: services.clone(),
+        age_seconds: updated_at.elapsed().as_secs(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_stores;
+
+    fn docker_unavailable() -> Result<DockerClient, String> {
+        Err("Failed to connect to Docker: no daemon".to_string())
+    }
+
+    // Creating the client doesn't contact the daemon, so any address will do
+    fn docker_client() -> Result<DockerClient, String> {
+        DockerClient::connect_remote("tcp://127.0.0.1:9", None, None, None)
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn fresh_install_is_ready_without_docker() {
+        let stores = isolated_stores();
+        let docker = Mutex::new(None);
+
+        let result = prepare_subsystems(&docker, docker_unavailable).await;
+
+        assert!(result.config_valid);
+        assert!(result.directories_ready);
+        assert!(!result.docker_connected);
+        assert_eq!(result.warnings, vec!["Failed to connect to Docker: no daemon"]);
+        for dir in ["projects", "ssl", "nginx"] {
+            assert!(stores.home().join("SignalforgeData").join(dir).is_dir());
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn docker_is_connected_once() {
+        let _stores = isolated_stores();
+        let docker = Mutex::new(None);
+
+        assert!(prepare_subsystems(&docker, docker_client).await.docker_connected);
+        // An existing client is kept rather than reconnecting
+        assert!(prepare_subsystems(&docker, docker_unavailable).await.docker_connected);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn invalid_config_is_reported_but_directories_are_created() {
+        let _stores = isolated_stores();
+        let app_config = config::AppConfig {
+            default_network_subnet: "not-a-subnet".to_string(),
+            ..Default::default()
+        };
+        config::save_app_config(app_config).await.unwrap();
+
+        let result = prepare_subsystems(&Mutex::new(None), docker_client).await;
+
+        assert!(!result.config_valid);
+        assert!(result.directories_ready);
+        assert_eq!(result.warnings, vec!["Invalid default network subnet: not-a-subnet"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn unreadable_config_leaves_directories_unprepared() {
+        let stores = isolated_stores();
+        let config_dir = stores.root.path().join("config/signalforge-dev");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(config_dir.join("config.json"), "{").unwrap();
+
+        let result = prepare_subsystems(&Mutex::new(None), docker_client).await;
+
+        assert!(!result.config_valid);
+        assert!(!result.directories_ready);
+        assert!(result.docker_connected);
+        assert!(result.warnings.iter().all(|w| w.starts_with("Failed to parse config")));
+        assert!(!stores.home().join("SignalforgeData").exists());
+    }
+
+    #[tokio::test]
+    async fn finished_subscriptions_are_pruned_on_insert() {
+        let mut subscriptions = HashMap::new();
+        let ended = tokio::spawn(async {});
+        while !ended.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        subscriptions.insert("stopped".to_string(), ended);
+        subscriptions.insert("running".to_string(), tokio::spawn(std::future::pending()));
+
+        insert_subscription(&mut subscriptions, "new".to_string(), tokio::spawn(std::future::pending()));
+
+        let mut ids: Vec<&String> = subscriptions.keys().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["new", "running"]);
+    }
+
+    #[tokio::test]
+    async fn resubscribing_aborts_the_previous_stream() {
+        let mut subscriptions = HashMap::new();
+        insert_subscription(&mut subscriptions, "web".to_string(), tokio::spawn(std::future::pending()));
+        let replacement = tokio::spawn(std::future::pending());
+
+        insert_subscription(&mut subscriptions, "web".to_string(), replacement);
+
+        assert_eq!(subscriptions.len(), 1);
+        assert!(!subscriptions["web"].is_finished());
+    }
+}
@@ -1,9 +1,30 @@
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+// Bump whenever AppConfig changes in a way that needs migrate_config_file
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+// Directory fields that default to subfolders of ~/SignalforgeData
+const PATH_FIELDS: [(&str, &str); 3] = [
+    ("projects_dir", "projects"),
+    ("ssl_dir", "ssl"),
+    ("nginx_conf_dir", "nginx"),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MigrationResult {
+    pub migrated: bool,
+    pub fields_added: Vec<String>,
+    pub backup_path: Option<String>,
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
+    #[serde(default)]
+    pub schema_version: u32,
     pub projects_dir: String,
     pub ssl_dir: String,
     pub nginx_conf_dir: String,
@@ -11,6 +32,77 @@ pub struct AppConfig {
     pub default_php_version: String,
     pub default_mysql_version: String,
     pub default_postgres_version: String,
+    #[serde(default)]
+    pub preferred_context: Option<String>,
+    #[serde(default)]
+    pub auto_open_browser: bool,
+    #[serde(default)]
+    pub telemetry_enabled: bool,
+    #[serde(default)]
+    pub telemetry_endpoint: Option<String>,
+    #[serde(default = "default_log_retention_days")]
+    pub log_retention_days: u32,
+    #[serde(default = "default_max_log_entries")]
+    pub max_log_entries: u32,
+    #[serde(default = "default_use_buildkit")]
+    pub use_buildkit: bool,
+    #[serde(default = "default_restart_policy")]
+    pub default_restart_policy: String,
+    #[serde(default)]
+    pub remote_docker: Option<RemoteDockerConfig>,
+    #[serde(default)]
+    pub registry_credentials: Vec<RegistryCredential>,
+}
+
+// The password lives in the OS keychain and is never written to config.json
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RegistryCredential {
+    pub server: String,
+    pub username: String,
+    #[serde(default, skip_serializing)]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteDockerConfig {
+    pub host: String,
+    pub ca_path: Option<String>,
+    pub cert_path: Option<String>,
+    pub key_path: Option<String>,
+}
+
+// Keychain service name registry passwords and secret keys are stored under
+pub(crate) const KEYCHAIN_SERVICE: &str = "signalforge-dev";
+
+// Values docker compose accepts for `restart:`
+const RESTART_POLICIES: [&str; 4] = ["no", "always", "on-failure", "unless-stopped"];
+
+fn default_log_retention_days() -> u32 {
+    30
+}
+
+fn default_max_log_entries() -> u32 {
+    1000
+}
+
+fn default_use_buildkit() -> bool {
+    true
+}
+
+fn default_restart_policy() -> String {
+    "unless-stopped".to_string()
+}
+
+pub(crate) fn validate_restart_policy(policy: &str) -> Result<(), String> {
+    if RESTART_POLICIES.contains(&policy) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid restart policy '{}', expected one of: {}",
+            policy,
+            RESTART_POLICIES.join(", ")
+        ))
+    }
 }
 
 impl Default for AppConfig {
@@ -19,6 +111,7 @@ impl Default for AppConfig {
         let signalforge_dir = home.join("SignalforgeData");
 
         Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
             projects_dir: signalforge_dir.join("projects").to_string_lossy().to_string(),
             ssl_dir: signalforge_dir.join("ssl").to_string_lossy().to_string(),
             nginx_conf_dir: signalforge_dir.join("nginx").to_string_lossy().to_string(),
@@ -26,6 +119,16 @@ impl Default for AppConfig {
             default_php_version: "8.4".to_string(),
             default_mysql_version: "8".to_string(),
             default_postgres_version: "17".to_string(),
+            preferred_context: None,
+            auto_open_browser: false,
+            telemetry_enabled: false,
+            telemetry_endpoint: None,
+            log_retention_days: default_log_retention_days(),
+            max_log_entries: default_max_log_entries(),
+            use_buildkit: default_use_buildkit(),
+            default_restart_policy: default_restart_policy(),
+            remote_docker: None,
+            registry_credentials: Vec::new(),
         }
     }
 }
@@ -37,8 +140,148 @@ fn get_config_path() -> PathBuf {
         .join("config.json")
 }
 
+// Returns a description of every problem found; an empty list means valid
+pub(crate) fn validate_app_config(config: &AppConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for (field, dir) in [
+        ("projects_dir", &config.projects_dir),
+        ("ssl_dir", &config.ssl_dir),
+        ("nginx_conf_dir", &config.nginx_conf_dir),
+    ] {
+        if !Path::new(dir).is_absolute() {
+            problems.push(format!("{} must be an absolute path: {}", field, dir));
+        }
+    }
+
+    let subnet_valid = config
+        .default_network_subnet
+        .split_once('/')
+        .is_some_and(|(addr, prefix)| {
+            addr.parse::<std::net::Ipv4Addr>().is_ok() && prefix.parse::<u8>().is_ok_and(|p| p <= 32)
+        });
+    if !subnet_valid {
+        problems.push(format!("Invalid default network subnet: {}", config.default_network_subnet));
+    }
+
+    if let Err(e) = validate_restart_policy(&config.default_restart_policy) {
+        problems.push(e);
+    }
+
+    if config.telemetry_enabled && config.telemetry_endpoint.is_none() {
+        problems.push("Telemetry is enabled but no endpoint is configured".to_string());
+    }
+
+    problems
+}
+
 #[tauri::command]
 pub async fn get_app_config() -> Result<AppConfig, String> {
+    migrate_config_file().await?;
+    load_app_config()
+}
+
+// The SignalforgeData folder the user actually configured, taken from
+// whichever directory field is already present
+fn existing_data_root(config: &serde_json::Map<String, Value>) -> Option<PathBuf> {
+    PATH_FIELDS.iter().find_map(|(field, _)| {
+        config
+            .get(*field)
+            .and_then(Value::as_str)
+            .and_then(|dir| Path::new(dir).parent())
+            .map(Path::to_path_buf)
+    })
+}
+
+// Fills in fields missing from an older config.json. Plain serde defaults
+// would point missing directories at this machine's home folder, so those
+// are derived from the directories the user already configured instead.
+#[tauri::command]
+pub async fn migrate_config_file() -> Result<MigrationResult, String> {
+    let config_path = get_config_path();
+
+    let unchanged = MigrationResult {
+        migrated: false,
+        fields_added: Vec::new(),
+        backup_path: None,
+    };
+
+    if !config_path.exists() {
+        return Ok(unchanged);
+    }
+
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config: {}", e))?;
+    let mut current: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse config: {}", e))?;
+    let current = current
+        .as_object_mut()
+        .ok_or_else(|| "Failed to parse config: expected a JSON object".to_string())?;
+
+    let schema_version = current
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32;
+
+    let defaults = serde_json::to_value(AppConfig::default())
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let defaults = defaults
+        .as_object()
+        .ok_or_else(|| "Failed to serialize config".to_string())?;
+
+    let data_root = existing_data_root(current);
+
+    let mut fields_added = Vec::new();
+    for (key, default) in defaults {
+        if key == "schema_version" || current.contains_key(key) {
+            continue;
+        }
+
+        let sub_dir = PATH_FIELDS
+            .iter()
+            .find(|(field, _)| field == key)
+            .map(|(_, sub_dir)| sub_dir);
+
+        let value = match (sub_dir, &data_root) {
+            (Some(sub_dir), Some(root)) => Value::String(root.join(sub_dir).to_string_lossy().to_string()),
+            _ => default.clone(),
+        };
+
+        current.insert(key.clone(), value);
+        fields_added.push(key.clone());
+    }
+
+    if fields_added.is_empty() && schema_version >= CURRENT_SCHEMA_VERSION {
+        return Ok(unchanged);
+    }
+
+    let backup_path = config_path.with_file_name(format!(
+        "config.v{}.{}.json.bak",
+        schema_version,
+        Utc::now().format("%Y%m%d%H%M%S")
+    ));
+    fs::copy(&config_path, &backup_path)
+        .map_err(|e| format!("Failed to back up config: {}", e))?;
+
+    current.insert(
+        "schema_version".to_string(),
+        Value::from(CURRENT_SCHEMA_VERSION.max(schema_version)),
+    );
+    fields_added.sort();
+
+    let migrated = serde_json::to_string_pretty(current)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    fs::write(&config_path, migrated)
+        .map_err(|e| format!("Failed to write config: {}", e))?;
+
+    Ok(MigrationResult {
+        migrated: true,
+        fields_added,
+        backup_path: Some(backup_path.to_string_lossy().to_string()),
+    })
+}
+
+pub(crate) fn load_app_config() -> Result<AppConfig, String> {
     let config_path = get_config_path();
 
     if !config_path.exists() {
@@ -56,6 +299,7 @@ pub async fn get_app_config() -> Result<AppConfig, String> {
 
 #[tauri::command]
 pub async fn save_app_config(config: AppConfig) -> Result<(), String> {
+    validate_restart_policy(&config.default_restart_policy)?;
     save_app_config_internal(&config)
 }
 
@@ -101,3 +345,122 @@ pub async fn reset_app_config() -> Result<AppConfig, String> {
     save_app_config_internal(&config)?;
     Ok(config)
 }
+
+#[tauri::command]
+pub async fn set_auto_open_browser(enabled: bool) -> Result<(), String> {
+    let mut config = get_app_config().await?;
+    config.auto_open_browser = enabled;
+    save_app_config_internal(&config)
+}
+
+#[tauri::command]
+pub async fn set_telemetry(enabled: bool, endpoint: Option<String>) -> Result<(), String> {
+    if let Some(url) = &endpoint {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(format!("Invalid telemetry endpoint: {}", url));
+        }
+    }
+
+    let mut config = get_app_config().await?;
+    config.telemetry_enabled = enabled;
+    config.telemetry_endpoint = endpoint;
+    save_app_config_internal(&config)
+}
+
+// Reduces "https://index.docker.io/v1/" and similar to the bare registry host
+pub fn normalize_registry_server(server: &str) -> String {
+    let host = server
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split('/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match host.as_str() {
+        "index.docker.io" | "registry-1.docker.io" | "registry.hub.docker.com" => "docker.io".to_string(),
+        _ => host,
+    }
+}
+
+fn registry_keychain_entry(server: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &format!("registry:{}", server))
+        .map_err(|e| format!("Failed to open keychain: {}", e))
+}
+
+// Looks up the stored credential for a registry host, password included
+pub fn find_registry_credential(registry: &str) -> Result<Option<RegistryCredential>, String> {
+    let registry = normalize_registry_server(registry);
+    let config = load_app_config()?;
+
+    let Some(mut credential) = config
+        .registry_credentials
+        .into_iter()
+        .find(|c| c.server == registry)
+    else {
+        return Ok(None);
+    };
+
+    credential.password = registry_keychain_entry(&credential.server)?
+        .get_password()
+        .map_err(|e| format!("Failed to read registry password from keychain: {}", e))?;
+
+    Ok(Some(credential))
+}
+
+#[tauri::command]
+pub async fn list_registry_credentials() -> Result<Vec<RegistryCredential>, String> {
+    Ok(get_app_config().await?.registry_credentials)
+}
+
+#[tauri::command]
+pub async fn add_registry_credential(
+    server: String,
+    username: String,
+    password: String,
+) -> Result<RegistryCredential, String> {
+    let server = normalize_registry_server(&server);
+    if server.is_empty() {
+        return Err("Registry server must not be empty".to_string());
+    }
+    if username.is_empty() || password.is_empty() {
+        return Err("Username and password are required".to_string());
+    }
+
+    registry_keychain_entry(&server)?
+        .set_password(&password)
+        .map_err(|e| format!("Failed to save registry password to keychain: {}", e))?;
+
+    let credential = RegistryCredential {
+        server,
+        username,
+        password: String::new(),
+    };
+
+    let mut config = get_app_config().await?;
+    config.registry_credentials.retain(|c| c.server != credential.server);
+    config.registry_credentials.push(credential.clone());
+    save_app_config_internal(&config)?;
+
+    Ok(credential)
+}
+
+#[tauri::command]
+pub async fn remove_registry_credential(server: String) -> Result<(), String> {
+    let server = normalize_registry_server(&server);
+
+    let mut config = get_app_config().await?;
+    let before = config.registry_credentials.len();
+    config.registry_credentials.retain(|c| c.server != server);
+    if config.registry_credentials.len() == before {
+        return Err(format!("No credential stored for {}", server));
+    }
+    save_app_config_internal(&config)?;
+
+    // A missing keychain entry just means there is nothing left to clean up
+    match registry_keychain_entry(&server)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove registry password from keychain: {}", e)),
+    }
+}
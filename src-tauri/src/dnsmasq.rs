@@ -1,8 +1,15 @@
+use futures_util::stream::{self, StreamExt};
+use hickory_proto::op::{Message, MessageType, OpCode, Query};
+use hickory_proto::rr::{Name, RecordType};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 use std::net::TcpStream;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DnsmasqStatus {
@@ -10,6 +17,7 @@ pub struct DnsmasqStatus {
     pub running: bool,
     pub config_path: Option<String>,
     pub sig_configured: bool,
+    pub global_cache_ttl: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -19,6 +27,16 @@ pub struct SigDomain {
     pub ip_address: String,
     pub in_hosts: bool,
     pub in_dnsmasq: bool,
+    // Informational only: dnsmasq's cache TTL options are global, and local
+    // records are served without going through the cache anyway
+    #[serde(default = "default_ttl_seconds")]
+    pub ttl_seconds: u32,
+    #[serde(default)]
+    pub alias_for: Option<String>,
+}
+
+fn default_ttl_seconds() -> u32 {
+    60
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -29,6 +47,43 @@ pub struct DnsTestResult {
     pub method: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsBatchTestResult {
+    pub all_resolved: bool,
+    pub results: Vec<DnsTestResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResolutionSummary {
+    pub resolved_count: usize,
+    pub failed_count: usize,
+    pub failed_domains: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsConflict {
+    pub domain: String,
+    pub hosts_ip: String,
+    pub dnsmasq_ip: String,
+    pub conflict_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsHop {
+    pub server: String,
+    pub query: String,
+    pub answer: Option<String>,
+    pub latency_ms: u64,
+    pub differs_from_previous: bool,
+}
+
+// Resolver used as the last hop when tracing a domain
+const PUBLIC_DNS_SERVER: &str = "8.8.8.8:53";
+const DNS_QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Maximum number of resolution tests running at once in test_all_domains
+const DNS_TEST_CONCURRENCY: usize = 5;
+
 fn get_domains_file() -> PathBuf {
     dirs::config_dir()
         .unwrap_or_else(|| PathBuf::from("/tmp"))
@@ -77,6 +132,101 @@ fn get_dnsmasq_config_path() -> Option<PathBuf> {
     possible_paths.into_iter().find(|p| p.exists())
 }
 
+fn get_domain_conf_path(config_dir: &std::path::Path, domain: &SigDomain) -> PathBuf {
+    config_dir.join(format!("sig-{}.conf", domain.name.replace('.', "_")))
+}
+
+// Written by apply_domains_to_dnsmasq; once it exists it holds every record
+const DOMAINS_CONF: &str = "sig-domains.conf";
+
+// dnsmasq only follows a cname= to a name it knows from /etc/hosts, DHCP or
+// another cname, so an alias whose target isn't in /etc/hosts gets a plain
+// address record instead
+fn domain_record_line(domain: &SigDomain, domains: &[SigDomain]) -> String {
+    let target_in_hosts = |target: &String| {
+        domains
+            .iter()
+            .any(|d| &d.full_domain == target && d.alias_for.is_none() && d.in_hosts)
+    };
+
+    match &domain.alias_for {
+        Some(target) if target_in_hosts(target) => {
+            format!("cname={},{}", domain.full_domain, target)
+        }
+        _ => format!("address=/{}/{}", domain.full_domain, domain.ip_address),
+    }
+}
+
+fn generate_domain_conf(domain: &SigDomain, domains: &[SigDomain]) -> String {
+    format!(
+        "# Signalforge Dev - {}\n{}\n",
+        domain.full_domain,
+        domain_record_line(domain, domains)
+    )
+}
+
+// Keeps each record in exactly one file, since dnsmasq refuses to start on a
+// duplicate cname=: the aggregate file once it exists, per-domain files
+// before that. Updates `in_dnsmasq` to match what was written.
+fn sync_domain_records(domains: &mut [SigDomain]) {
+    let Some(config_dir) = get_dnsmasq_config_path() else {
+        domains.iter_mut().for_each(|d| d.in_dnsmasq = false);
+        return;
+    };
+
+    let snapshot = domains.to_vec();
+    let aggregate = config_dir.join(DOMAINS_CONF);
+
+    if aggregate.exists() {
+        let written = fs::write(&aggregate, generate_domains_conf(&snapshot)).is_ok();
+        for domain in domains.iter_mut() {
+            remove_domain_conf(domain);
+            domain.in_dnsmasq = written;
+        }
+    } else {
+        for domain in domains.iter_mut() {
+            let path = get_domain_conf_path(&config_dir, domain);
+            domain.in_dnsmasq = fs::write(path, generate_domain_conf(domain, &snapshot)).is_ok();
+        }
+    }
+}
+
+fn remove_domain_conf(domain: &SigDomain) {
+    if let Some(config_dir) = get_dnsmasq_config_path() {
+        let path = get_domain_conf_path(&config_dir, domain);
+        if path.exists() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn parse_min_cache_ttl(text: &str) -> Option<u32> {
+    let re = Regex::new(r"(?m)(?:^|\s)(?:--)?min-cache-ttl=(\d+)").ok()?;
+    re.captures(text)
+        .and_then(|caps| caps[1].parse().ok())
+}
+
+// Reads --min-cache-ttl from the running process, falling back to dnsmasq.conf
+fn get_global_cache_ttl() -> Option<u32> {
+    let from_process = Command::new("pgrep")
+        .args(["-a", "dnsmasq"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| parse_min_cache_ttl(&String::from_utf8_lossy(&o.stdout)));
+
+    from_process.or_else(|| {
+        [
+            "/etc/dnsmasq.conf",
+            "/usr/local/etc/dnsmasq.conf",
+            "/opt/homebrew/etc/dnsmasq.conf",
+        ]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|content| parse_min_cache_ttl(&content))
+    })
+}
+
 fn check_dnsmasq_running() -> bool {
     let output = Command::new("pgrep")
         .arg("dnsmasq")
@@ -112,6 +262,7 @@ pub async fn get_dnsmasq_status() -> Result<DnsmasqStatus, String> {
         running,
         config_path,
         sig_configured,
+        global_cache_ttl: get_global_cache_ttl(),
     })
 }
 
@@ -131,31 +282,117 @@ address=/.sig/127.0.0.1
     fs::write(&sig_conf, config_content)
         .map_err(|e| format!("Failed to write dnsmasq config: {}. You may need sudo permissions.", e))?;
 
-    // Try to restart dnsmasq
-    let restart_result = Command::new("sudo")
+    if restart_dnsmasq_service() {
+        Ok("dnsmasq configured for .sig TLD and restarted successfully".to_string())
+    } else {
+        Ok("dnsmasq configured. Please restart dnsmasq manually.".to_string())
+    }
+}
+
+// Restarts dnsmasq via systemctl, falling back to brew services on macOS
+fn restart_dnsmasq_service() -> bool {
+    let restarted = Command::new("sudo")
         .args(["systemctl", "restart", "dnsmasq"])
-        .output();
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    restarted
+        || Command::new("brew")
+            .args(["services", "restart", "dnsmasq"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+}
+
+// Exit status and stdout of a command; reload goes through this so tests can
+// stand in for pgrep and kill
+type CommandRunner<'a> = dyn FnMut(&str, &[&str]) -> std::io::Result<(bool, String)> + 'a;
+
+fn run_command(program: &str, args: &[&str]) -> std::io::Result<(bool, String)> {
+    let output = Command::new(program).args(args).output()?;
+    Ok((output.status.success(), String::from_utf8_lossy(&output.stdout).to_string()))
+}
+
+fn find_dnsmasq_pid(run: &mut CommandRunner) -> Option<String> {
+    run("pgrep", &["-x", "dnsmasq"])
+        .ok()
+        .filter(|(success, _)| *success)
+        .and_then(|(_, stdout)| stdout.lines().next().map(|pid| pid.trim().to_string()))
+        .filter(|pid| !pid.is_empty())
+}
+
+// SIGHUP makes dnsmasq clear its cache and re-read /etc/hosts and its
+// hosts/resolv files without dropping in-flight queries. It does not re-read
+// dnsmasq.conf or conf-dir, so config rewrites still need a full restart.
+#[tauri::command]
+pub async fn reload_dnsmasq() -> Result<String, String> {
+    reload_dnsmasq_with(std::env::consts::OS, &mut run_command)
+}
+
+fn reload_dnsmasq_with(os: &str, run: &mut CommandRunner) -> Result<String, String> {
+    if let Some(pid) = find_dnsmasq_pid(run) {
+        let (signalled, _) = run("sudo", &["kill", "-HUP", &pid])
+            .map_err(|e| format!("Failed to signal dnsmasq: {}", e))?;
 
-    match restart_result {
-        Ok(output) if output.status.success() => {
-            Ok("dnsmasq configured for .sig TLD and restarted successfully".to_string())
+        if signalled {
+            return Ok(format!("Sent SIGHUP to dnsmasq (pid {})", pid));
         }
-        _ => {
-            // Try brew services for macOS
-            let brew_result = Command::new("brew")
-                .args(["services", "restart", "dnsmasq"])
-                .output();
-
-            match brew_result {
-                Ok(output) if output.status.success() => {
-                    Ok("dnsmasq configured for .sig TLD and restarted successfully".to_string())
-                }
-                _ => {
-                    Ok("dnsmasq configured. Please restart dnsmasq manually.".to_string())
-                }
-            }
+    }
+
+    // Homebrew has no reload action, so fall back to restarting the service
+    if os == "macos" {
+        let (restarted, _) = run("brew", &["services", "restart", "dnsmasq"])
+            .map_err(|e| format!("Failed to run brew services: {}", e))?;
+
+        if restarted {
+            return Ok("Restarted dnsmasq via brew services".to_string());
         }
     }
+
+    Err("dnsmasq is not running or could not be signalled".to_string())
+}
+
+fn generate_domains_conf(domains: &[SigDomain]) -> String {
+    let mut content = String::from("# Signalforge Dev - per-domain .sig entries\n");
+
+    for domain in domains {
+        content.push_str(&domain_record_line(domain, domains));
+        content.push('\n');
+    }
+
+    content
+}
+
+#[tauri::command]
+pub async fn export_domains_as_dnsmasq_conf(dest_path: String) -> Result<(), String> {
+    let domains = load_domains()?;
+
+    fs::write(&dest_path, generate_domains_conf(&domains))
+        .map_err(|e| format!("Failed to write dnsmasq config: {}", e))
+}
+
+#[tauri::command]
+pub async fn apply_domains_to_dnsmasq() -> Result<String, String> {
+    let config_dir = get_dnsmasq_config_path()
+        .ok_or_else(|| "dnsmasq config directory not found. Please install dnsmasq first.".to_string())?;
+
+    let domains_conf = config_dir.join(DOMAINS_CONF);
+
+    export_domains_as_dnsmasq_conf(domains_conf.to_string_lossy().to_string())
+        .await
+        .map_err(|e| format!("{}. You may need sudo permissions.", e))?;
+
+    // The aggregate file now holds every record; drop the per-domain copies
+    let mut domains = load_domains()?;
+    sync_domain_records(&mut domains);
+    save_domains(&domains)?;
+
+    if restart_dnsmasq_service() {
+        Ok("dnsmasq domain entries applied and dnsmasq restarted successfully".to_string())
+    } else {
+        Ok("dnsmasq domain entries written. Please restart dnsmasq manually.".to_string())
+    }
 }
 
 #[tauri::command]
@@ -164,7 +401,11 @@ pub async fn list_sig_domains() -> Result<Vec<SigDomain>, String> {
 }
 
 #[tauri::command]
-pub async fn add_sig_domain(name: String, ip_address: Option<String>) -> Result<SigDomain, String> {
+pub async fn add_sig_domain(
+    name: String,
+    ip_address: Option<String>,
+    ttl_seconds: Option<u32>,
+) -> Result<SigDomain, String> {
     let mut domains = load_domains()?;
 
     let full_domain = if name.ends_with(".sig") {
@@ -183,21 +424,21 @@ pub async fn add_sig_domain(name: String, ip_address: Option<String>) -> Result<
     // Add to /etc/hosts
     let add_to_hosts = add_hosts_entry_internal(&full_domain, &ip);
 
-    let domain = SigDomain {
+    domains.push(SigDomain {
         name: name.trim_end_matches(".sig").to_string(),
         full_domain: full_domain.clone(),
         ip_address: ip,
         in_hosts: add_to_hosts.is_ok(),
-        in_dnsmasq: false, // dnsmasq handles wildcards, individual entries not needed
-    };
-
-    domains.push(domain.clone());
+        in_dnsmasq: false,
+        ttl_seconds: ttl_seconds.unwrap_or_else(default_ttl_seconds),
+        alias_for: None,
+    });
+    // Aliases of this name may now be able to use cname=
+    sync_domain_records(&mut domains);
     save_domains(&domains)?;
 
-    if add_to_hosts.is_err() {
-        return Ok(domain); // Return domain but note hosts wasn't updated
-    }
-
+    // Returned even when /etc/hosts wasn't updated; in_hosts says so
+    let domain = domains.last().cloned().ok_or("Failed to add domain")?;
     Ok(domain)
 }
 
@@ -244,15 +485,121 @@ pub async fn remove_sig_domain(name: String) -> Result<(), String> {
         .position(|d| d.full_domain == full_domain)
         .ok_or_else(|| format!("Domain not found: {}", full_domain))?;
 
-    // Try to remove from /etc/hosts
+    // Try to remove from /etc/hosts and dnsmasq
     let _ = remove_hosts_entry_internal(&full_domain);
+    remove_domain_conf(&domains[idx]);
+
+    domains.remove(idx);
+    // Aliases of the removed name fall back to address records
+    sync_domain_records(&mut domains);
+    save_domains(&domains)?;
+
+    Ok(())
+}
+
+// Builds the alias record for `source`, taking its IP and TTL from the
+// target so both names resolve to the same address
+fn new_alias(source: &str, target_full: &str, domains: &[SigDomain]) -> Result<SigDomain, String> {
+    if domains.iter().any(|d| d.full_domain == source) {
+        return Err(format!("Domain '{}' already exists", source));
+    }
+
+    let target = domains
+        .iter()
+        .find(|d| d.full_domain == target_full)
+        .ok_or_else(|| format!("Domain not found: {}", target_full))?;
+
+    // Point at the real record so an alias never resolves through another alias
+    let canonical = target.alias_for.clone().unwrap_or_else(|| target.full_domain.clone());
+
+    Ok(SigDomain {
+        name: source.trim_end_matches(".sig").to_string(),
+        full_domain: source.to_string(),
+        ip_address: target.ip_address.clone(),
+        in_hosts: false,
+        in_dnsmasq: false,
+        ttl_seconds: target.ttl_seconds,
+        alias_for: Some(canonical),
+    })
+}
+
+#[tauri::command]
+pub async fn add_domain_alias(source_domain: String, target_domain: String) -> Result<SigDomain, String> {
+    let mut domains = load_domains()?;
+
+    let source = if source_domain.ends_with(".sig") {
+        source_domain.clone()
+    } else {
+        format!("{}.sig", source_domain)
+    };
+    let target_full = if target_domain.ends_with(".sig") {
+        target_domain.clone()
+    } else {
+        format!("{}.sig", target_domain)
+    };
+
+    let mut alias = new_alias(&source, &target_full, &domains)?;
+    alias.in_hosts = add_hosts_entry_internal(&alias.full_domain, &alias.ip_address).is_ok();
+    domains.push(alias);
+    sync_domain_records(&mut domains);
+    save_domains(&domains)?;
+
+    let domain = domains.last().cloned().ok_or("Failed to add alias")?;
+    Ok(domain)
+}
+
+#[tauri::command]
+pub async fn remove_domain_alias(source_domain: String) -> Result<(), String> {
+    let mut domains = load_domains()?;
+
+    let source = if source_domain.ends_with(".sig") {
+        source_domain.clone()
+    } else {
+        format!("{}.sig", source_domain)
+    };
+
+    let idx = domains
+        .iter()
+        .position(|d| d.full_domain == source)
+        .ok_or_else(|| format!("Domain not found: {}", source))?;
+
+    if domains[idx].alias_for.is_none() {
+        return Err(format!("Domain '{}' is not an alias", source));
+    }
+
+    let _ = remove_hosts_entry_internal(&source);
+    remove_domain_conf(&domains[idx]);
 
     domains.remove(idx);
+    sync_domain_records(&mut domains);
     save_domains(&domains)?;
 
     Ok(())
 }
 
+#[tauri::command]
+pub async fn update_domain_ttl(name: String, ttl: u32) -> Result<SigDomain, String> {
+    let mut domains = load_domains()?;
+
+    let full_domain = if name.ends_with(".sig") {
+        name.clone()
+    } else {
+        format!("{}.sig", name)
+    };
+
+    let domain = domains
+        .iter_mut()
+        .find(|d| d.full_domain == full_domain)
+        .ok_or_else(|| format!("Domain not found: {}", full_domain))?;
+
+    domain.ttl_seconds = ttl;
+    let updated = domain.clone();
+
+    save_domains(&domains)?;
+
+    Ok(updated)
+}
+
 fn remove_hosts_entry_internal(domain: &str) -> Result<(), String> {
     let hosts_path = PathBuf::from("/etc/hosts");
 
@@ -284,6 +631,11 @@ pub async fn test_domain_resolution(domain: String) -> Result<DnsTestResult, Str
         format!("{}.sig", domain)
     };
 
+    Ok(resolve_domain(full_domain))
+}
+
+// Blocks on getent and a TCP connect, so batch callers run it off the runtime
+fn resolve_domain(full_domain: String) -> DnsTestResult {
     // Try to resolve using getent/host command
     let output = Command::new("getent")
         .args(["hosts", &full_domain])
@@ -294,34 +646,214 @@ pub async fn test_domain_resolution(domain: String) -> Result<DnsTestResult, Str
             let stdout = String::from_utf8_lossy(&output.stdout);
             let ip = stdout.split_whitespace().next().map(String::from);
 
-            return Ok(DnsTestResult {
+            return DnsTestResult {
                 domain: full_domain,
                 resolves: true,
                 ip_address: ip,
                 method: "getent".to_string(),
-            });
+            };
         }
     }
 
     // Fallback: try to connect to port 80
     let test_addr = format!("{}:80", full_domain);
     if TcpStream::connect(&test_addr).is_ok() {
-        return Ok(DnsTestResult {
+        return DnsTestResult {
             domain: full_domain,
             resolves: true,
             ip_address: Some("127.0.0.1".to_string()),
             method: "tcp_connect".to_string(),
-        });
+        };
     }
 
-    Ok(DnsTestResult {
+    unresolved(full_domain)
+}
+
+fn unresolved(full_domain: String) -> DnsTestResult {
+    DnsTestResult {
         domain: full_domain,
         resolves: false,
         ip_address: None,
         method: "none".to_string(),
+    }
+}
+
+fn summarize_resolution(results: &[DnsTestResult]) -> ResolutionSummary {
+    let failed_domains: Vec<String> = results
+        .iter()
+        .filter(|r| !r.resolves)
+        .map(|r| r.domain.clone())
+        .collect();
+
+    ResolutionSummary {
+        resolved_count: results.len() - failed_domains.len(),
+        failed_count: failed_domains.len(),
+        failed_domains,
+    }
+}
+
+#[tauri::command]
+pub async fn test_all_domains() -> Result<DnsBatchTestResult, String> {
+    let domains = load_domains()?;
+    let results = resolve_all(domains, resolve_domain).await;
+
+    Ok(DnsBatchTestResult {
+        all_resolved: results.iter().all(|r| r.resolves),
+        results,
     })
 }
 
+// Each test shells out and blocks, so run them on the blocking pool with at
+// most DNS_TEST_CONCURRENCY in flight. A task that dies counts as an
+// unresolved domain rather than failing the whole batch.
+async fn resolve_all(domains: Vec<SigDomain>, resolve: fn(String) -> DnsTestResult) -> Vec<DnsTestResult> {
+    let mut indexed: Vec<(usize, DnsTestResult)> = stream::iter(domains.into_iter().enumerate())
+        .map(|(i, d)| async move {
+            let full_domain = d.full_domain;
+            let fallback = full_domain.clone();
+            let result = tokio::task::spawn_blocking(move || resolve(full_domain))
+                .await
+                .unwrap_or_else(|_| unresolved(fallback));
+            (i, result)
+        })
+        .buffer_unordered(DNS_TEST_CONCURRENCY)
+        .collect()
+        .await;
+
+    // Report in the same order as the domain list
+    indexed.sort_by_key(|(i, _)| *i);
+    indexed.into_iter().map(|(_, r)| r).collect()
+}
+
+#[tauri::command]
+pub async fn get_domain_resolution_summary() -> Result<ResolutionSummary, String> {
+    let batch = test_all_domains().await?;
+    Ok(summarize_resolution(&batch.results))
+}
+
+// First `server=` upstream from the dnsmasq config, ignoring domain-scoped
+// entries like `server=/example/10.0.0.1`
+fn get_dnsmasq_upstream() -> Option<String> {
+    let mut files: Vec<PathBuf> = [
+        "/etc/dnsmasq.conf",
+        "/usr/local/etc/dnsmasq.conf",
+        "/opt/homebrew/etc/dnsmasq.conf",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .collect();
+
+    if let Some(config_dir) = get_dnsmasq_config_path() {
+        if let Ok(entries) = fs::read_dir(config_dir) {
+            files.extend(entries.flatten().map(|e| e.path()));
+        }
+    }
+
+    files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| content.lines().map(str::to_string).collect::<Vec<_>>())
+        .filter_map(|line| line.trim().strip_prefix("server=").map(str::to_string))
+        .find(|server| !server.starts_with('/'))
+        .map(|server| {
+            // dnsmasq uses `ip#port` for non-standard ports
+            match server.split_once('#') {
+                Some((ip, port)) => format!("{}:{}", ip, port),
+                None => format!("{}:53", server),
+            }
+        })
+}
+
+async fn query_dns_server(server: &str, domain: &str) -> Result<Vec<String>, String> {
+    let name = Name::from_ascii(domain).map_err(|e| format!("Invalid domain {}: {}", domain, e))?;
+
+    let id_bytes = Uuid::new_v4();
+    let id = u16::from_be_bytes([id_bytes.as_bytes()[0], id_bytes.as_bytes()[1]]);
+
+    let mut request = Message::new();
+    request
+        .set_id(id)
+        .set_message_type(MessageType::Query)
+        .set_op_code(OpCode::Query)
+        .set_recursion_desired(true)
+        .add_query(Query::query(name, RecordType::A));
+
+    let packet = request
+        .to_vec()
+        .map_err(|e| format!("Failed to encode DNS query: {}", e))?;
+
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .await
+        .map_err(|e| format!("Failed to open UDP socket: {}", e))?;
+    socket
+        .send_to(&packet, server)
+        .await
+        .map_err(|e| format!("Failed to query {}: {}", server, e))?;
+
+    let mut buf = [0u8; 512];
+    let len = tokio::time::timeout(DNS_QUERY_TIMEOUT, socket.recv(&mut buf))
+        .await
+        .map_err(|_| format!("Timed out waiting for {}", server))?
+        .map_err(|e| format!("Failed to read response from {}: {}", server, e))?;
+
+    let response = Message::from_vec(&buf[..len])
+        .map_err(|e| format!("Invalid DNS response from {}: {}", server, e))?;
+
+    if response.id() != id {
+        return Err(format!("Mismatched DNS response id from {}", server));
+    }
+
+    Ok(response
+        .answers()
+        .iter()
+        .filter_map(|record| record.data().map(|data| data.to_string()))
+        .collect())
+}
+
+// Queries dnsmasq, its upstream and a public resolver in turn, flagging the
+// hop where the answer first changes
+#[tauri::command]
+pub async fn trace_dns_resolution(domain: String) -> Result<Vec<DnsHop>, String> {
+    let mut servers = vec!["127.0.0.1:53".to_string()];
+    if let Some(upstream) = get_dnsmasq_upstream() {
+        servers.push(upstream);
+    }
+    servers.push(PUBLIC_DNS_SERVER.to_string());
+
+    Ok(trace_servers(servers, &domain).await)
+}
+
+async fn trace_servers(servers: Vec<String>, domain: &str) -> Vec<DnsHop> {
+    let mut hops: Vec<DnsHop> = Vec::new();
+
+    for server in servers {
+        let started = Instant::now();
+        let answer = match query_dns_server(&server, domain).await {
+            Ok(records) if records.is_empty() => None,
+            Ok(mut records) => {
+                records.sort();
+                Some(records.join(", "))
+            }
+            Err(e) => Some(format!("error: {}", e)),
+        };
+
+        let differs_from_previous = hops
+            .last()
+            .map(|previous| previous.answer != answer)
+            .unwrap_or(false);
+
+        hops.push(DnsHop {
+            server,
+            query: format!("{} A", domain),
+            answer,
+            latency_ms: started.elapsed().as_millis() as u64,
+            differs_from_previous,
+        });
+    }
+
+    hops
+}
+
 #[tauri::command]
 pub async fn get_hosts_entries() -> Result<Vec<SigDomain>, String> {
     let hosts_path = PathBuf::from("/etc/hosts");
@@ -344,6 +876,8 @@ pub async fn get_hosts_entries() -> Result<Vec<SigDomain>, String> {
                         ip_address: ip,
                         in_hosts: true,
                         in_dnsmasq: false,
+                        ttl_seconds: default_ttl_seconds(),
+                        alias_for: None,
                     });
                 }
             }
@@ -354,6 +888,39 @@ pub async fn get_hosts_entries() -> Result<Vec<SigDomain>, String> {
     Ok(sig_entries)
 }
 
+fn detect_dns_conflicts(hosts: &[SigDomain], domains: &[SigDomain]) -> Vec<DnsConflict> {
+    let mut conflicts = Vec::new();
+
+    for domain in domains.iter().filter(|d| d.in_dnsmasq) {
+        for entry in hosts.iter().filter(|h| h.full_domain == domain.full_domain) {
+            let conflict_type = if entry.ip_address == domain.ip_address {
+                "duplicate"
+            } else {
+                "ip_mismatch"
+            };
+
+            conflicts.push(DnsConflict {
+                domain: domain.full_domain.clone(),
+                hosts_ip: entry.ip_address.clone(),
+                dnsmasq_ip: domain.ip_address.clone(),
+                conflict_type: conflict_type.to_string(),
+            });
+        }
+    }
+
+    conflicts
+}
+
+// Domains defined both in /etc/hosts and in dnsmasq resolve differently
+// depending on resolver order; a differing IP is the harmful case
+#[tauri::command]
+pub async fn find_dns_conflicts() -> Result<Vec<DnsConflict>, String> {
+    let hosts = get_hosts_entries().await?;
+    let domains = list_sig_domains().await?;
+
+    Ok(detect_dns_conflicts(&hosts, &domains))
+}
+
 #[tauri::command]
 pub async fn get_dnsmasq_install_instructions() -> Result<String, String> {
     let os = std::env::consts::OS;
@@ -403,3 +970,295 @@ After installation, click "Configure .sig TLD" to set up the configuration."#,
 
     Ok(instructions.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn domain(name: &str, ip: &str) -> SigDomain {
+        SigDomain {
+            name: name.to_string(),
+            full_domain: format!("{}.sig", name),
+            ip_address: ip.to_string(),
+            in_hosts: true,
+            in_dnsmasq: false,
+            ttl_seconds: default_ttl_seconds(),
+            alias_for: None,
+        }
+    }
+
+    #[test]
+    fn domain_conf_has_record_but_no_cache_ttl() {
+        let mut shop = domain("shop", "127.0.0.1");
+        shop.ttl_seconds = 300;
+
+        let conf = generate_domain_conf(&shop, std::slice::from_ref(&shop));
+
+        assert_eq!(conf, "# Signalforge Dev - shop.sig\naddress=/shop.sig/127.0.0.1\n");
+        // min-cache-ttl is global in dnsmasq, so the TTL is never written
+        assert!(!conf.contains("min-cache-ttl"));
+    }
+
+    #[test]
+    fn domains_conf_lists_one_address_per_domain() {
+        let domains = vec![
+            domain("shop", "127.0.0.1"),
+            domain("api.shop", "127.0.0.1"),
+            domain("blog", "172.26.0.10"),
+        ];
+
+        assert_eq!(
+            generate_domains_conf(&domains),
+            "# Signalforge Dev - per-domain .sig entries\n\
+             address=/shop.sig/127.0.0.1\n\
+             address=/api.shop.sig/127.0.0.1\n\
+             address=/blog.sig/172.26.0.10\n"
+        );
+    }
+
+    #[test]
+    fn alias_takes_ip_from_its_target() {
+        let mut blog = domain("blog", "172.26.0.10");
+        blog.ttl_seconds = 300;
+
+        let alias = new_alias("www.blog.sig", "blog.sig", &[blog]).unwrap();
+
+        assert_eq!(alias.name, "www.blog");
+        assert_eq!(alias.ip_address, "172.26.0.10");
+        assert_eq!(alias.ttl_seconds, 300);
+        assert_eq!(alias.alias_for.as_deref(), Some("blog.sig"));
+    }
+
+    #[test]
+    fn alias_of_an_alias_points_at_the_real_record() {
+        let blog = domain("blog", "172.26.0.10");
+        let www = new_alias("www.blog.sig", "blog.sig", std::slice::from_ref(&blog)).unwrap();
+
+        let alias = new_alias("m.blog.sig", "www.blog.sig", &[blog, www]).unwrap();
+
+        assert_eq!(alias.alias_for.as_deref(), Some("blog.sig"));
+        assert_eq!(alias.ip_address, "172.26.0.10");
+    }
+
+    #[test]
+    fn alias_requires_a_known_target_and_a_free_name() {
+        let blog = domain("blog", "172.26.0.10");
+
+        let missing = new_alias("www.shop.sig", "shop.sig", std::slice::from_ref(&blog));
+        assert_eq!(missing.unwrap_err(), "Domain not found: shop.sig");
+
+        let taken = new_alias("blog.sig", "blog.sig", &[blog]);
+        assert_eq!(taken.unwrap_err(), "Domain 'blog.sig' already exists");
+    }
+
+    #[test]
+    fn alias_is_written_as_cname_when_target_is_in_hosts() {
+        let blog = domain("blog", "172.26.0.10");
+        let www = new_alias("www.blog.sig", "blog.sig", std::slice::from_ref(&blog)).unwrap();
+        let domains = vec![blog, www];
+
+        assert_eq!(
+            generate_domain_conf(&domains[1], &domains),
+            "# Signalforge Dev - www.blog.sig\ncname=www.blog.sig,blog.sig\n"
+        );
+    }
+
+    #[test]
+    fn alias_falls_back_to_address_when_target_is_not_in_hosts() {
+        let mut blog = domain("blog", "172.26.0.10");
+        blog.in_hosts = false;
+        let www = new_alias("www.blog.sig", "blog.sig", std::slice::from_ref(&blog)).unwrap();
+        let domains = vec![blog, www];
+
+        // dnsmasq only answers a cname= for targets it can resolve locally
+        assert_eq!(domain_record_line(&domains[1], &domains), "address=/www.blog.sig/172.26.0.10");
+    }
+
+    // Stand-in for getent: anything under blog.sig fails, and a name
+    // containing "panic" kills the blocking task
+    fn mock_resolve(full_domain: String) -> DnsTestResult {
+        if full_domain.contains("panic") {
+            panic!("resolver crashed");
+        }
+        if full_domain.ends_with("blog.sig") {
+            return unresolved(full_domain);
+        }
+        DnsTestResult {
+            domain: full_domain,
+            resolves: true,
+            ip_address: Some("127.0.0.1".to_string()),
+            method: "mock".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_all_keeps_domain_order() {
+        let domains: Vec<SigDomain> = (0..12).map(|i| domain(&format!("app{}", i), "127.0.0.1")).collect();
+
+        let results = resolve_all(domains, mock_resolve).await;
+
+        let names: Vec<String> = results.iter().map(|r| r.domain.clone()).collect();
+        let expected: Vec<String> = (0..12).map(|i| format!("app{}.sig", i)).collect();
+        assert_eq!(names, expected);
+        assert!(results.iter().all(|r| r.resolves && r.method == "mock"));
+    }
+
+    #[tokio::test]
+    async fn resolve_all_reports_failures_and_crashed_tasks() {
+        let domains = vec![
+            domain("shop", "127.0.0.1"),
+            domain("blog", "172.26.0.10"),
+            domain("panic", "127.0.0.1"),
+        ];
+
+        let results = resolve_all(domains, mock_resolve).await;
+        let summary = summarize_resolution(&results);
+
+        assert_eq!(results[2].domain, "panic.sig");
+        assert_eq!(results[2].method, "none");
+        assert_eq!(summary.resolved_count, 1);
+        assert_eq!(summary.failed_count, 2);
+        assert_eq!(summary.failed_domains, vec!["blog.sig", "panic.sig"]);
+    }
+
+    #[test]
+    fn hosts_and_dnsmasq_conflicts_are_classified() {
+        let mut shop = domain("shop", "127.0.0.1");
+        let mut blog = domain("blog", "127.0.0.1");
+        let mut api = domain("api", "127.0.0.1");
+        for d in [&mut shop, &mut blog, &mut api] {
+            d.in_dnsmasq = true;
+        }
+        let hosts = vec![
+            domain("shop", "127.0.0.1"),
+            domain("blog", "172.26.0.10"),
+            domain("docs", "127.0.0.1"),
+        ];
+
+        let conflicts = detect_dns_conflicts(&hosts, &[shop, blog, api]);
+
+        let found: Vec<(&str, &str, &str, &str)> = conflicts
+            .iter()
+            .map(|c| (c.domain.as_str(), c.hosts_ip.as_str(), c.dnsmasq_ip.as_str(), c.conflict_type.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("shop.sig", "127.0.0.1", "127.0.0.1", "duplicate"),
+                ("blog.sig", "172.26.0.10", "127.0.0.1", "ip_mismatch"),
+            ]
+        );
+    }
+
+    #[test]
+    fn domains_missing_from_dnsmasq_do_not_conflict() {
+        let hosts = vec![domain("shop", "172.26.0.10")];
+
+        assert!(detect_dns_conflicts(&hosts, &[domain("shop", "127.0.0.1")]).is_empty());
+    }
+
+    // Records every command and answers pgrep with `pgrep_stdout`
+    fn fake_runner<'a>(
+        calls: &'a mut Vec<String>,
+        pgrep_stdout: &'a str,
+    ) -> impl FnMut(&str, &[&str]) -> std::io::Result<(bool, String)> + 'a {
+        move |program, args| {
+            calls.push(format!("{} {}", program, args.join(" ")));
+            match program {
+                "pgrep" => Ok((!pgrep_stdout.is_empty(), pgrep_stdout.to_string())),
+                _ => Ok((true, String::new())),
+            }
+        }
+    }
+
+    #[test]
+    fn reload_sends_sighup_to_the_pgrep_pid() {
+        let mut calls = Vec::new();
+
+        let result = reload_dnsmasq_with("linux", &mut fake_runner(&mut calls, "4242\n"));
+
+        assert_eq!(result.unwrap(), "Sent SIGHUP to dnsmasq (pid 4242)");
+        assert_eq!(calls, vec!["pgrep -x dnsmasq", "sudo kill -HUP 4242"]);
+    }
+
+    #[test]
+    fn reload_without_a_pid_sends_nothing() {
+        let mut calls = Vec::new();
+
+        let result = reload_dnsmasq_with("linux", &mut fake_runner(&mut calls, ""));
+
+        assert_eq!(result.unwrap_err(), "dnsmasq is not running or could not be signalled");
+        assert_eq!(calls, vec!["pgrep -x dnsmasq"]);
+    }
+
+    #[test]
+    fn reload_on_macos_falls_back_to_brew_services() {
+        let mut calls = Vec::new();
+
+        let result = reload_dnsmasq_with("macos", &mut fake_runner(&mut calls, ""));
+
+        assert_eq!(result.unwrap(), "Restarted dnsmasq via brew services");
+        assert_eq!(calls, vec!["pgrep -x dnsmasq", "brew services restart dnsmasq"]);
+    }
+
+    // Answers a single A query with `ip` and returns the address to query
+    async fn mock_dns_server(ip: Ipv4Addr) -> String {
+        use hickory_proto::rr::{rdata::A, RData, Record};
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap().to_string();
+
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            let (len, peer) = socket.recv_from(&mut buf).await.unwrap();
+            let request = Message::from_vec(&buf[..len]).unwrap();
+            let query = request.queries()[0].clone();
+
+            let mut response = Message::new();
+            response
+                .set_id(request.id())
+                .set_message_type(MessageType::Response)
+                .set_op_code(OpCode::Query)
+                .add_answer(Record::from_rdata(query.name().clone(), 60, RData::A(A(ip))))
+                .add_query(query);
+            socket.send_to(&response.to_vec().unwrap(), peer).await.unwrap();
+        });
+
+        addr
+    }
+
+    #[tokio::test]
+    async fn trace_reports_each_server_answer() {
+        let dnsmasq = mock_dns_server(Ipv4Addr::new(127, 0, 0, 1)).await;
+        let upstream = mock_dns_server(Ipv4Addr::new(172, 26, 0, 10)).await;
+
+        let hops = trace_servers(vec![dnsmasq.clone(), upstream.clone()], "shop.sig").await;
+
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[0].server, dnsmasq);
+        assert_eq!(hops[0].query, "shop.sig A");
+        assert_eq!(hops[0].answer.as_deref(), Some("127.0.0.1"));
+        assert!(!hops[0].differs_from_previous);
+        assert_eq!(hops[1].server, upstream);
+        assert_eq!(hops[1].answer.as_deref(), Some("172.26.0.10"));
+        assert!(hops[1].differs_from_previous);
+    }
+
+    #[test]
+    fn parse_min_cache_ttl_reads_flag_and_config_line() {
+        assert_eq!(parse_min_cache_ttl("dnsmasq --keep-in-foreground --min-cache-ttl=120"), Some(120));
+        assert_eq!(parse_min_cache_ttl("# comment\nmin-cache-ttl=300\ncache-size=1000\n"), Some(300));
+        assert_eq!(parse_min_cache_ttl("cache-size=1000\n"), None);
+    }
+
+    #[test]
+    fn ttl_defaults_when_missing_from_stored_domains() {
+        let stored = r#"{"name":"shop","full_domain":"shop.sig","ip_address":"127.0.0.1","in_hosts":true,"in_dnsmasq":true}"#;
+
+        let parsed: SigDomain = serde_json::from_str(stored).unwrap();
+
+        assert_eq!(parsed.ttl_seconds, 60);
+        assert!(parsed.alias_for.is_none());
+    }
+}
@@ -1,18 +1,56 @@
 use bollard::container::{
     ListContainersOptions, StartContainerOptions, StopContainerOptions, RestartContainerOptions,
-    Stats, StatsOptions, InspectContainerOptions, LogsOptions,
+    Stats, StatsOptions, InspectContainerOptions, LogsOptions, DownloadFromContainerOptions,
+    UpdateContainerOptions, UploadToContainerOptions, LogOutput, Config, CreateContainerOptions,
+    RemoveContainerOptions, PruneContainersOptions, KillContainerOptions, WaitContainerOptions,
 };
-use bollard::models::HealthStatusEnum;
-use bollard::Docker;
+use bollard::exec::{CreateExecOptions, StartExecResults};
+use bollard::image::{
+    CreateImageOptions, ListImagesOptions, PruneImagesOptions, RemoveImageOptions, TagImageOptions,
+};
+use bollard::models::{HealthStatusEnum, HostConfig, Ipam, IpamConfig, Network, Volume};
+use bollard::network::{
+    CreateNetworkOptions, InspectNetworkOptions, ListNetworksOptions, PruneNetworksOptions,
+};
+use bollard::volume::{
+    CreateVolumeOptions, ListVolumesOptions, PruneVolumesOptions, RemoveVolumeOptions,
+};
+use bollard::auth::DockerCredentials;
+use bollard::{Docker, API_DEFAULT_VERSION};
+use futures_util::future::join_all;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::{Cursor, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 // Container name prefix for signalforge managed containers
 const SIGNALFORGE_PREFIX: &str = "signalforge-";
 
+// Connection timeout (seconds) used when connecting to a context endpoint
+const CONNECT_TIMEOUT: u64 = 120;
+
+// Labels docker compose sets on every container it creates
+const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+const COMPOSE_VERSION_LABEL: &str = "com.docker.compose.version";
+
+// POSIX signal names kill_container accepts
+const KILL_SIGNALS: [&str; 15] = [
+    "SIGHUP", "SIGINT", "SIGQUIT", "SIGABRT", "SIGKILL", "SIGUSR1", "SIGUSR2", "SIGPIPE",
+    "SIGALRM", "SIGTERM", "SIGCONT", "SIGSTOP", "SIGTSTP", "SIGWINCH", "SIGPWR",
+];
+
+// Image used for the throwaway container that reads a volume during backup
+const VOLUME_BACKUP_IMAGE: &str = "alpine:3";
+
+// Smallest memory limit Docker accepts for a container (6MB)
+const MIN_MEMORY_BYTES: u64 = 6 * 1024 * 1024;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ContainerInfo {
     pub id: String,
@@ -22,6 +60,37 @@ pub struct ContainerInfo {
     pub state: String,
     pub created: i64,
     pub ports: Vec<PortMapping>,
+    #[serde(default)]
+    pub compose_project: Option<String>,
+    #[serde(default)]
+    pub compose_service: Option<String>,
+    // Only in the inspect response, so filled in when a listing asks for
+    // limits and left empty otherwise
+    #[serde(default)]
+    pub memory_limit: Option<u64>,
+    #[serde(default)]
+    pub cpu_quota: Option<i64>,
+    #[serde(default)]
+    pub cpu_period: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContainerLabels {
+    pub compose_project: Option<String>,
+    pub compose_service: Option<String>,
+    pub compose_version: Option<String>,
+    pub labels: HashMap<String, String>,
+}
+
+impl ContainerLabels {
+    pub fn from_labels(labels: HashMap<String, String>) -> Self {
+        Self {
+            compose_project: labels.get(COMPOSE_PROJECT_LABEL).cloned(),
+            compose_service: labels.get(COMPOSE_SERVICE_LABEL).cloned(),
+            compose_version: labels.get(COMPOSE_VERSION_LABEL).cloned(),
+            labels,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -31,6 +100,15 @@ pub struct PortMapping {
     pub port_type: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResourceLimits {
+    pub memory_bytes: Option<i64>,
+    pub memory_swap_bytes: Option<i64>,
+    pub cpu_quota: Option<i64>,
+    pub cpu_period: Option<i64>,
+    pub pids_limit: Option<i64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ContainerStats {
     pub cpu_percent: f64,
@@ -54,6 +132,158 @@ pub struct DockerInfo {
     pub cpus: i64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CopyFromResult {
+    pub files_extracted: u32,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DockerImage {
+    pub id: String,
+    pub repo_tags: Vec<String>,
+    pub size: i64,
+    pub created: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImagePullEvent {
+    pub status: String,
+    pub progress: Option<i64>,
+    pub total: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DockerVolume {
+    pub name: String,
+    pub driver: String,
+    pub mountpoint: String,
+    pub created_at: Option<String>,
+    pub labels: HashMap<String, String>,
+}
+
+impl From<Volume> for DockerVolume {
+    fn from(volume: Volume) -> Self {
+        Self {
+            name: volume.name,
+            driver: volume.driver,
+            mountpoint: volume.mountpoint,
+            created_at: volume.created_at,
+            labels: volume.labels,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DockerNetwork {
+    pub id: String,
+    pub name: String,
+    pub driver: String,
+    pub subnet: Option<String>,
+    pub gateway: Option<String>,
+    pub containers: Vec<String>,
+}
+
+impl From<Network> for DockerNetwork {
+    fn from(network: Network) -> Self {
+        let ipam_config = network
+            .ipam
+            .and_then(|ipam| ipam.config)
+            .and_then(|config| config.into_iter().next())
+            .unwrap_or_default();
+
+        let mut containers: Vec<String> = network
+            .containers
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(id, container)| container.name.unwrap_or(id))
+            .collect();
+        containers.sort();
+
+        Self {
+            id: network.id.unwrap_or_default(),
+            name: network.name.unwrap_or_default(),
+            driver: network.driver.unwrap_or_default(),
+            subnet: ipam_config.subnet,
+            gateway: ipam_config.gateway,
+            containers,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DockerDiskUsage {
+    pub images_size: u64,
+    pub containers_size: u64,
+    pub volumes_size: u64,
+    pub build_cache_size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PruneResult {
+    pub space_reclaimed: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExecResult {
+    pub exit_code: i64,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CopyToResult {
+    pub files_copied: u32,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DockerContext {
+    pub name: String,
+    pub description: String,
+    pub endpoint: String,
+    pub current: bool,
+}
+
+// Shape of a single entry in `docker context ls --format json`
+#[derive(Debug, Deserialize)]
+struct RawDockerContext {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Description", default)]
+    description: String,
+    #[serde(rename = "DockerEndpoint", default)]
+    docker_endpoint: String,
+    #[serde(rename = "Current", default)]
+    current: bool,
+}
+
+// Shape of `docker context inspect` output, trimmed to what connecting needs
+#[derive(Debug, Deserialize)]
+struct RawContextInspect {
+    #[serde(rename = "Endpoints", default)]
+    endpoints: HashMap<String, RawContextEndpoint>,
+    #[serde(rename = "TLSMaterial", default)]
+    tls_material: HashMap<String, Vec<String>>,
+    #[serde(rename = "Storage", default)]
+    storage: RawContextStorage,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawContextEndpoint {
+    #[serde(rename = "Host", default)]
+    host: String,
+    #[serde(rename = "SkipTLSVerify", default)]
+    skip_tls_verify: bool,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct RawContextStorage {
+    #[serde(rename = "TLSPath", default)]
+    tls_path: String,
+}
+
+#[derive(Clone)]
 pub struct DockerClient {
     client: Arc<Mutex<Docker>>,
 }
@@ -68,149 +298,1343 @@ impl DockerClient {
         })
     }
 
-    pub async fn list_containers(&self) -> Result<Vec<ContainerInfo>, String> {
-        let docker = self.client.lock().await;
+    // Connects to a Docker daemon over TCP, using TLS when certificates are given.
+    pub fn connect_remote(
+        host: &str,
+        tls_ca: Option<&Path>,
+        tls_cert: Option<&Path>,
+        tls_key: Option<&Path>,
+    ) -> Result<Self, String> {
+        let docker = match (tls_ca, tls_cert, tls_key) {
+            (Some(ca), Some(cert), Some(key)) => {
+                Docker::connect_with_ssl(host, key, cert, ca, CONNECT_TIMEOUT, API_DEFAULT_VERSION)
+            }
+            (None, None, None) => {
+                Docker::connect_with_http(host, CONNECT_TIMEOUT, API_DEFAULT_VERSION)
+            }
+            _ => {
+                return Err(
+                    "TLS requires a CA certificate, client certificate and client key".to_string(),
+                )
+            }
+        };
+
+        let docker = docker.map_err(|e| format!("Failed to connect to Docker: {}", e))?;
+
+        Ok(Self {
+            client: Arc::new(Mutex::new(docker)),
+        })
+    }
+
+    // Connects to the endpoint of the named Docker context.
+    pub fn from_context(name: &str) -> Result<Self, String> {
+        let docker = connect_context(name)?;
+
+        Ok(Self {
+            client: Arc::new(Mutex::new(docker)),
+        })
+    }
+
+    // Replaces the underlying connection with one to the named Docker context.
+    pub async fn reconnect(&self, name: &str) -> Result<(), String> {
+        let docker = connect_context(name)?;
+        *self.client.lock().await = docker;
+        Ok(())
+    }
+
+    pub fn list_contexts() -> Result<Vec<DockerContext>, String> {
+        let output = Command::new("docker")
+            .args(["context", "ls", "--format", "json"])
+            .output()
+            .map_err(|e| format!("Failed to list Docker contexts: {}", e))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        }
+
+        parse_context_list(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    pub fn use_context(name: &str) -> Result<(), String> {
+        let output = Command::new("docker")
+            .args(["context", "use", name])
+            .output()
+            .map_err(|e| format!("Failed to switch Docker context: {}", e))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+
+    // The password is written to stdin so it never appears in the process list
+    pub fn login(server: &str, username: &str, password: &str) -> Result<String, String> {
+        let mut child = Command::new("docker")
+            .args(["login", "-u", username, "--password-stdin", server])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to run docker login: {}", e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(password.as_bytes())
+                .map_err(|e| format!("Failed to pass password to docker login: {}", e))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("Failed to run docker login: {}", e))?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+
+    pub fn logout(server: &str) -> Result<(), String> {
+        let output = Command::new("docker")
+            .args(["logout", server])
+            .output()
+            .map_err(|e| format!("Failed to run docker logout: {}", e))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+
+    pub async fn list_containers(
+        &self,
+        filter_labels: Option<HashMap<String, String>>,
+        include_limits: bool,
+    ) -> Result<Vec<ContainerInfo>, String> {
+        let mut containers = self.query_containers(filter_labels, false).await?;
+        if include_limits {
+            self.fill_resource_limits(&mut containers).await;
+        }
+        Ok(containers)
+    }
+
+    // One inspect per container, run concurrently; containers that can't be
+    // inspected keep empty limits
+    async fn fill_resource_limits(&self, containers: &mut [ContainerInfo]) {
+        let docker = self.client.lock().await;
+
+        let inspected = join_all(
+            containers
+                .iter()
+                .map(|c| docker.inspect_container(&c.id, None::<InspectContainerOptions>)),
+        )
+        .await;
+
+        for (info, inspect) in containers.iter_mut().zip(inspected) {
+            if let Some(host_config) = inspect.ok().and_then(|i| i.host_config) {
+                let limits = resource_limits(&host_config);
+                info.memory_limit = limits.memory_bytes.map(|m| m as u64);
+                info.cpu_quota = limits.cpu_quota;
+                info.cpu_period = limits.cpu_period;
+            }
+        }
+    }
+
+    pub async fn list_all_containers(
+        &self,
+        include_non_signalforge: bool,
+    ) -> Result<Vec<ContainerInfo>, String> {
+        self.query_containers(None, include_non_signalforge).await
+    }
+
+    async fn query_containers(
+        &self,
+        filter_labels: Option<HashMap<String, String>>,
+        include_non_signalforge: bool,
+    ) -> Result<Vec<ContainerInfo>, String> {
+        let docker = self.client.lock().await;
+
+        let mut filters: HashMap<String, Vec<String>> = HashMap::new();
+        if let Some(labels) = filter_labels {
+            let label_filters: Vec<String> = labels
+                .into_iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            if !label_filters.is_empty() {
+                filters.insert("label".to_string(), label_filters);
+            }
+        }
+
+        let options = ListContainersOptions::<String> {
+            all: true,
+            filters,
+            ..Default::default()
+        };
+
+        let containers = docker
+            .list_containers(Some(options))
+            .await
+            .map_err(|e| format!("Failed to list containers: {}", e))?;
+
+        let container_infos: Vec<ContainerInfo> = containers
+            .into_iter()
+            .filter_map(|c| {
+                let name = c.names
+                    .as_ref()
+                    .and_then(|names| names.first())
+                    .map(|n| n.trim_start_matches('/').to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+
+                // Only include signalforge containers unless explicitly requested
+                if !include_non_signalforge && !name.starts_with(SIGNALFORGE_PREFIX) {
+                    return None;
+                }
+
+                let ports = c.ports
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|p| PortMapping {
+                        private_port: p.private_port,
+                        public_port: p.public_port,
+                        port_type: p.typ.map(|t| format!("{:?}", t)).unwrap_or_default(),
+                    })
+                    .collect();
+
+                let labels = c.labels.unwrap_or_default();
+
+                Some(ContainerInfo {
+                    id: c.id.unwrap_or_default(),
+                    name,
+                    image: c.image.unwrap_or_default(),
+                    status: c.status.unwrap_or_default(),
+                    state: c.state.unwrap_or_default(),
+                    created: c.created.unwrap_or(0),
+                    ports,
+                    compose_project: labels.get(COMPOSE_PROJECT_LABEL).cloned(),
+                    compose_service: labels.get(COMPOSE_SERVICE_LABEL).cloned(),
+                    memory_limit: None,
+                    cpu_quota: None,
+                    cpu_period: None,
+                })
+            })
+            .collect();
+
+        Ok(container_infos)
+    }
+
+    pub async fn start_container(&self, id: &str) -> Result<(), String> {
+        let docker = self.client.lock().await;
+        docker
+            .start_container(id, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| format!("Failed to start container: {}", e))
+    }
+
+    pub async fn stop_container(&self, id: &str) -> Result<(), String> {
+        let docker = self.client.lock().await;
+        docker
+            .stop_container(id, Some(StopContainerOptions { t: 10 }))
+            .await
+            .map_err(|e| format!("Failed to stop container: {}", e))
+    }
+
+    pub async fn restart_container(&self, id: &str) -> Result<(), String> {
+        let docker = self.client.lock().await;
+        docker
+            .restart_container(id, Some(RestartContainerOptions { t: 10 }))
+            .await
+            .map_err(|e| format!("Failed to restart container: {}", e))
+    }
+
+    pub async fn kill_container(&self, id: &str, signal: &str) -> Result<(), String> {
+        let signal = normalize_signal(signal)?;
+        let docker = self.client.lock().await;
+
+        docker
+            .kill_container(id, Some(KillContainerOptions { signal }))
+            .await
+            .map_err(|e| format!("Failed to kill container: {}", e))
+    }
+
+    pub async fn update_container_resources(
+        &self,
+        id: &str,
+        memory_bytes: Option<u64>,
+        cpu_quota: Option<i64>,
+        cpu_period: Option<u64>,
+    ) -> Result<(), String> {
+        if memory_bytes.is_none() && cpu_quota.is_none() && cpu_period.is_none() {
+            return Err("no update parameters specified".to_string());
+        }
+
+        if let Some(memory) = memory_bytes {
+            if memory < MIN_MEMORY_BYTES {
+                return Err(format!(
+                    "Memory limit must be at least {} bytes (6MB)",
+                    MIN_MEMORY_BYTES
+                ));
+            }
+        }
+
+        let docker = self.client.lock().await;
+
+        let options = UpdateContainerOptions::<String> {
+            memory: memory_bytes.map(|m| m as i64),
+            cpu_quota,
+            cpu_period: cpu_period.map(|p| p as i64),
+            ..Default::default()
+        };
+
+        docker
+            .update_container(id, options)
+            .await
+            .map_err(|e| format!("Failed to update container: {}", e))
+    }
+
+    pub async fn get_container_resource_limits(&self, id: String) -> Result<ResourceLimits, String> {
+        let docker = self.client.lock().await;
+
+        let inspect = docker
+            .inspect_container(&id, None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| format!("Failed to inspect container: {}", e))?;
+
+        Ok(resource_limits(&inspect.host_config.unwrap_or_default()))
+    }
+
+    pub async fn get_container_logs(
+        &self,
+        id: &str,
+        tail: Option<u64>,
+        since: Option<i64>,
+        until: Option<i64>,
+    ) -> Result<Vec<String>, String> {
+        let docker = self.client.lock().await;
+
+        // A time window returns every line in it unless a tail is also given
+        let default_tail = if since.is_some() || until.is_some() { "all" } else { "100" };
+
+        let options = LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            since: since.unwrap_or(0),
+            until: until.unwrap_or(0),
+            tail: tail.map(|t| t.to_string()).unwrap_or_else(|| default_tail.to_string()),
+            timestamps: true,
+            ..Default::default()
+        };
+
+        let mut stream = docker.logs(id, Some(options));
+        let mut logs = Vec::new();
+
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(output) => {
+                    let line = output.to_string();
+                    logs.push(line);
+                }
+                Err(e) => {
+                    return Err(format!("Failed to get logs: {}", e));
+                }
+            }
+        }
+
+        Ok(logs)
+    }
+
+    pub async fn list_volumes(&self) -> Result<Vec<DockerVolume>, String> {
+        let docker = self.client.lock().await;
+
+        let response = docker
+            .list_volumes(None::<ListVolumesOptions<String>>)
+            .await
+            .map_err(|e| format!("Failed to list volumes: {}", e))?;
+
+        let mut volumes: Vec<DockerVolume> = response
+            .volumes
+            .unwrap_or_default()
+            .into_iter()
+            .map(DockerVolume::from)
+            .collect();
+        volumes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(volumes)
+    }
+
+    pub async fn inspect_volume(&self, name: &str) -> Result<DockerVolume, String> {
+        let docker = self.client.lock().await;
+
+        docker
+            .inspect_volume(name)
+            .await
+            .map(DockerVolume::from)
+            .map_err(|e| format!("Failed to inspect volume: {}", e))
+    }
+
+    pub async fn create_volume(
+        &self,
+        name: &str,
+        driver: Option<String>,
+        labels: HashMap<String, String>,
+    ) -> Result<DockerVolume, String> {
+        let docker = self.client.lock().await;
+
+        let options = CreateVolumeOptions {
+            name: name.to_string(),
+            driver: driver.unwrap_or_else(|| "local".to_string()),
+            labels,
+            ..Default::default()
+        };
+
+        docker
+            .create_volume(options)
+            .await
+            .map(DockerVolume::from)
+            .map_err(|e| format!("Failed to create volume: {}", e))
+    }
+
+    pub async fn remove_volume(&self, name: &str, force: bool) -> Result<(), String> {
+        let docker = self.client.lock().await;
+
+        docker
+            .remove_volume(name, Some(RemoveVolumeOptions { force }))
+            .await
+            .map_err(|e| format!("Failed to remove volume: {}", e))
+    }
+
+    pub async fn list_networks(&self) -> Result<Vec<DockerNetwork>, String> {
+        let docker = self.client.lock().await;
+
+        let networks = docker
+            .list_networks(None::<ListNetworksOptions<String>>)
+            .await
+            .map_err(|e| format!("Failed to list networks: {}", e))?;
+
+        // The list endpoint leaves out attached containers, so inspect each one
+        let inspected = join_all(networks.iter().map(|network| {
+            docker.inspect_network(
+                network.id.as_deref().unwrap_or_default(),
+                None::<InspectNetworkOptions<String>>,
+            )
+        }))
+        .await;
+
+        let mut networks: Vec<DockerNetwork> = networks
+            .into_iter()
+            .zip(inspected)
+            .map(|(listed, inspected)| DockerNetwork::from(inspected.unwrap_or(listed)))
+            .collect();
+        networks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(networks)
+    }
+
+    pub async fn create_network(
+        &self,
+        name: &str,
+        driver: Option<String>,
+        subnet: Option<String>,
+        gateway: Option<String>,
+    ) -> Result<DockerNetwork, String> {
+        let docker = self.client.lock().await;
+
+        let ipam = Ipam {
+            config: subnet.is_some().then(|| {
+                vec![IpamConfig {
+                    subnet,
+                    gateway,
+                    ..Default::default()
+                }]
+            }),
+            ..Default::default()
+        };
+
+        let options = CreateNetworkOptions {
+            name: name.to_string(),
+            check_duplicate: true,
+            driver: driver.unwrap_or_else(|| "bridge".to_string()),
+            ipam,
+            ..Default::default()
+        };
+
+        let response = docker
+            .create_network(options)
+            .await
+            .map_err(|e| format!("Failed to create network: {}", e))?;
+
+        let id = response.id.unwrap_or_else(|| name.to_string());
+        docker
+            .inspect_network(&id, None::<InspectNetworkOptions<String>>)
+            .await
+            .map(DockerNetwork::from)
+            .map_err(|e| format!("Failed to inspect network: {}", e))
+    }
+
+    pub async fn remove_network(&self, id: &str) -> Result<(), String> {
+        let docker = self.client.lock().await;
+
+        docker
+            .remove_network(id)
+            .await
+            .map_err(|e| format!("Failed to remove network: {}", e))
+    }
+
+    // Mounts the volume read-only into a stopped Alpine container and downloads
+    // its contents as a tar archive. Going through the API rather than a bind
+    // mount keeps this working against remote Docker hosts.
+    pub async fn backup_volume(&self, name: &str, dest_path: &str) -> Result<u64, String> {
+        let docker = self.client.lock().await.clone();
+
+        docker
+            .inspect_volume(name)
+            .await
+            .map_err(|e| format!("Volume not found: {}", e))?;
+
+        let container = create_volume_helper(&docker, name, true, None).await?;
+
+        let download = async {
+            let mut stream = docker.download_from_container(
+                &container.id,
+                Some(DownloadFromContainerOptions { path: "/volume/." }),
+            );
+            let mut archive = Vec::new();
+
+            while let Some(chunk) = stream.next().await {
+                let bytes = chunk.map_err(|e| format!("Failed to read volume: {}", e))?;
+                archive.extend_from_slice(&bytes);
+            }
+
+            if let Some(parent) = Path::new(dest_path).parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+            }
+            std::fs::write(dest_path, &archive)
+                .map_err(|e| format!("Failed to write volume backup: {}", e))?;
+
+            Ok::<_, String>(archive.len() as u64)
+        };
+        let result = download.await;
+
+        // Always clean up the helper container, even when the download failed
+        remove_volume_helper(&docker, &container.id).await;
+
+        result
+    }
+
+    // Replaces the contents of a volume with a tar archive made by
+    // backup_volume. Running containers using the volume are stopped first
+    // and started again afterwards, also when the restore fails.
+    pub async fn restore_volume(&self, name: &str, archive_path: &str) -> Result<Vec<String>, String> {
+        let docker = self.client.lock().await.clone();
+
+        docker
+            .inspect_volume(name)
+            .await
+            .map_err(|e| format!("Volume not found: {}", e))?;
+
+        let archive = std::fs::read(archive_path)
+            .map_err(|e| format!("Failed to read volume backup: {}", e))?;
+
+        let users = docker
+            .list_containers(Some(ListContainersOptions::<String> {
+                filters: HashMap::from([("volume".to_string(), vec![name.to_string()])]),
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| format!("Failed to list containers: {}", e))?;
+        let mut stopped = Vec::new();
+        let mut stop_errors = Vec::new();
+
+        for id in users.into_iter().filter_map(|c| c.id) {
+            match docker.stop_container(&id, Some(StopContainerOptions { t: 10 })).await {
+                Ok(_) => stopped.push(id),
+                Err(e) => stop_errors.push(format!("Failed to stop container {}: {}", id, e)),
+            }
+        }
+
+        // Don't restore under a container that is still writing; bring back
+        // whatever was already stopped and report every failure
+        if !stop_errors.is_empty() {
+            for id in &stopped {
+                let _ = docker
+                    .start_container(id, None::<StartContainerOptions<String>>)
+                    .await;
+            }
+            return Err(stop_errors.join("; "));
+        }
+
+        let restore = async {
+            // Empty the volume first so files missing from the backup don't linger
+            let clear = vec![
+                "find".to_string(),
+                "/volume".to_string(),
+                "-mindepth".to_string(),
+                "1".to_string(),
+                "-delete".to_string(),
+            ];
+            let container = create_volume_helper(&docker, name, false, Some(clear)).await?;
+
+            let result = async {
+                docker
+                    .start_container(&container.id, None::<StartContainerOptions<String>>)
+                    .await
+                    .map_err(|e| format!("Failed to start restore container: {}", e))?;
+
+                let mut wait = docker.wait_container(&container.id, None::<WaitContainerOptions<String>>);
+                while let Some(status) = wait.next().await {
+                    status.map_err(|e| format!("Failed to clear volume: {}", e))?;
+                }
+
+                docker
+                    .upload_to_container(
+                        &container.id,
+                        Some(UploadToContainerOptions {
+                            path: "/volume",
+                            ..Default::default()
+                        }),
+                        archive.into(),
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to restore volume: {}", e))
+            }
+            .await;
+
+            remove_volume_helper(&docker, &container.id).await;
+            result
+        };
+        let result = restore.await;
+
+        for id in &stopped {
+            let _ = docker
+                .start_container(id, None::<StartContainerOptions<String>>)
+                .await;
+        }
+
+        result.map(|_| stopped)
+    }
+
+    pub async fn copy_from_container(
+        &self,
+        id: &str,
+        container_path: &str,
+        host_dest_dir: &str,
+    ) -> Result<CopyFromResult, String> {
+        // Large dumps take a while; don't hold the client lock for the download
+        let docker = self.client.lock().await.clone();
+
+        let options = DownloadFromContainerOptions {
+            path: container_path.to_string(),
+        };
+
+        let mut stream = docker.download_from_container(id, Some(options));
+        let mut archive = Vec::new();
+
+        while let Some(chunk) = stream.next().await {
+            let bytes = chunk.map_err(|e| format!("Failed to download from container: {}", e))?;
+            archive.extend_from_slice(&bytes);
+        }
+
+        let dest_dir = PathBuf::from(host_dest_dir);
+        tokio::task::spawn_blocking(move || extract_tar_archive(&archive, &dest_dir))
+            .await
+            .map_err(|e| format!("Failed to extract archive: {}", e))?
+    }
+
+    // Copies a host file or directory into `container_dir`, like `docker cp`
+    pub async fn copy_to_container(
+        &self,
+        id: &str,
+        host_path: &str,
+        container_dir: &str,
+    ) -> Result<CopyToResult, String> {
+        let (archive, result) = pack_tar_archive(Path::new(host_path)).await?;
+        self.upload_to_container(id, container_dir, archive).await?;
+        Ok(result)
+    }
+
+    // Follows the live stats stream, handing at most one sample per `interval`
+    // to `on_stats`. Returns when the container stops or the stream errors.
+    pub async fn stream_container_stats<F>(
+        &self,
+        id: &str,
+        interval: Duration,
+        mut on_stats: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(ContainerStats),
+    {
+        // Clone the handle so the stream doesn't hold the client lock
+        let docker = self.client.lock().await.clone();
+
+        let options = StatsOptions {
+            stream: true,
+            one_shot: false,
+        };
+
+        let mut stream = docker.stats(id, Some(options));
+        let mut last_emit: Option<Instant> = None;
+
+        while let Some(result) = stream.next().await {
+            let stats = result.map_err(|e| format!("Failed to stream stats: {}", e))?;
+
+            if last_emit.is_some_and(|t| t.elapsed() < interval) {
+                continue;
+            }
+            last_emit = Some(Instant::now());
+            on_stats(calculate_stats(&stats));
+        }
+
+        Ok(())
+    }
+
+    // Follows a container's log stream, handing each line to `on_line`.
+    // Returns once the container stops and Docker closes the stream.
+    pub async fn follow_container_logs<F>(&self, id: &str, mut on_line: F) -> Result<(), String>
+    where
+        F: FnMut(String),
+    {
+        let docker = self.client.lock().await.clone();
+
+        let options = LogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            stderr: true,
+            tail: "100".to_string(),
+            timestamps: true,
+            ..Default::default()
+        };
+
+        let mut stream = docker.logs(id, Some(options));
+
+        while let Some(result) = stream.next().await {
+            let output = result.map_err(|e| format!("Failed to follow logs: {}", e))?;
+            on_line(output.to_string());
+        }
+
+        Ok(())
+    }
+
+    pub async fn exec_container(
+        &self,
+        id: &str,
+        cmd: Vec<String>,
+        env: Option<Vec<String>>,
+        timeout: Duration,
+    ) -> Result<ExecResult, String> {
+        if cmd.is_empty() {
+            return Err("Command must not be empty".to_string());
+        }
+
+        // Commands like composer install can run for minutes
+        let docker = self.client.lock().await.clone();
+
+        let options = CreateExecOptions {
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            cmd: Some(cmd),
+            env,
+            ..Default::default()
+        };
+
+        let exec = docker
+            .create_exec(id, options)
+            .await
+            .map_err(|e| format!("Failed to create exec: {}", e))?;
+
+        let run = async {
+            let mut stdout = String::new();
+            let mut stderr = String::new();
+
+            if let StartExecResults::Attached { mut output, .. } = docker
+                .start_exec(&exec.id, None)
+                .await
+                .map_err(|e| format!("Failed to start exec: {}", e))?
+            {
+                while let Some(chunk) = output.next().await {
+                    match chunk.map_err(|e| format!("Failed to read exec output: {}", e))? {
+                        LogOutput::StdErr { message } => {
+                            stderr.push_str(&String::from_utf8_lossy(&message))
+                        }
+                        other => stdout.push_str(&other.to_string()),
+                    }
+                }
+            }
+
+            Ok::<_, String>((stdout, stderr))
+        };
+
+        let (stdout, stderr) = tokio::time::timeout(timeout, run)
+            .await
+            .map_err(|_| format!("Command did not finish within {} seconds", timeout.as_secs()))??;
+
+        let inspect = docker
+            .inspect_exec(&exec.id)
+            .await
+            .map_err(|e| format!("Failed to inspect exec: {}", e))?;
+
+        Ok(ExecResult {
+            exit_code: inspect.exit_code.unwrap_or(-1),
+            stdout,
+            stderr,
+        })
+    }
+
+    pub async fn upload_to_container(
+        &self,
+        id: &str,
+        container_dir: &str,
+        archive: Vec<u8>,
+    ) -> Result<(), String> {
+        let docker = self.client.lock().await;
+
+        let inspect = docker
+            .inspect_container(id, None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| format!("Container not found: {}", e))?;
+
+        if !inspect.state.and_then(|s| s.running).unwrap_or(false) {
+            return Err(format!("Container {} is not running", id));
+        }
+
+        let options = UploadToContainerOptions {
+            path: container_dir.to_string(),
+            ..Default::default()
+        };
+
+        docker
+            .upload_to_container(id, Some(options), archive.into())
+            .await
+            .map_err(|e| format!("Failed to upload to container: {}", e))
+    }
+
+    pub async fn get_container_stats(&self, id: &str) -> Result<ContainerStats, String> {
+        let docker = self.client.lock().await;
+
+        let options = StatsOptions {
+            stream: false,
+            one_shot: true,
+        };
+
+        let mut stream = docker.stats(id, Some(options));
+
+        if let Some(result) = stream.next().await {
+            let stats = result.map_err(|e| format!("Failed to get stats: {}", e))?;
+            return Ok(calculate_stats(&stats));
+        }
+
+        Err("No stats available".to_string())
+    }
+
+    pub async fn get_docker_info(&self) -> Result<DockerInfo, String> {
+        let docker = self.client.lock().await;
+
+        let info = docker
+            .info()
+            .await
+            .map_err(|e| format!("Failed to get Docker info: {}", e))?;
+
+        Ok(DockerInfo {
+            containers_running: info.containers_running.unwrap_or(0) as i64,
+            containers_paused: info.containers_paused.unwrap_or(0) as i64,
+            containers_stopped: info.containers_stopped.unwrap_or(0) as i64,
+            images: info.images.unwrap_or(0) as i64,
+            docker_version: info.server_version.unwrap_or_default(),
+            os_type: info.os_type.unwrap_or_default(),
+            architecture: info.architecture.unwrap_or_default(),
+            memory_total: info.mem_total.unwrap_or(0),
+            cpus: info.ncpu.unwrap_or(0) as i64,
+        })
+    }
+
+    pub async fn tag_image(&self, source: &str, repository: &str, tag: &str) -> Result<(), String> {
+        validate_image_reference(repository, tag)?;
+
+        let docker = self.client.lock().await;
+
+        let options = TagImageOptions {
+            repo: repository.to_string(),
+            tag: tag.to_string(),
+        };
+
+        docker
+            .tag_image(source, Some(options))
+            .await
+            .map_err(|e| format!("Failed to tag image: {}", e))
+    }
+
+    pub async fn list_images(&self) -> Result<Vec<DockerImage>, String> {
+        let docker = self.client.lock().await;
+
+        let images = docker
+            .list_images(Some(ListImagesOptions::<String> {
+                all: false,
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| format!("Failed to list images: {}", e))?;
+
+        Ok(images
+            .into_iter()
+            .map(|image| DockerImage {
+                id: image.id,
+                repo_tags: image.repo_tags,
+                size: image.size,
+                created: image.created,
+            })
+            .collect())
+    }
+
+    // Pulls an image, handing each progress update to `on_progress`.
+    pub async fn pull_image<F>(
+        &self,
+        name: &str,
+        credentials: Option<DockerCredentials>,
+        mut on_progress: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(ImagePullEvent),
+    {
+        // Pulls can take minutes; don't hold the client lock for all of it
+        let docker = self.client.lock().await.clone();
+
+        let (image, tag) = split_image_reference(name);
+        let options = CreateImageOptions {
+            from_image: image,
+            tag,
+            ..Default::default()
+        };
+
+        let mut stream = docker.create_image(Some(options), None, credentials);
+
+        while let Some(result) = stream.next().await {
+            let info = result.map_err(|e| format!("Failed to pull image: {}", e))?;
+            let detail = info.progress_detail.unwrap_or_default();
+
+            on_progress(ImagePullEvent {
+                status: info.status.unwrap_or_default(),
+                progress: detail.current,
+                total: detail.total,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove_image(&self, id: &str, force: bool) -> Result<(), String> {
+        let docker = self.client.lock().await;
+
+        let options = RemoveImageOptions {
+            force,
+            noprune: false,
+        };
+
+        docker
+            .remove_image(id, Some(options), None)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to remove image: {}", e))
+    }
+
+    pub async fn list_image_tags(&self, image_name: &str) -> Result<Vec<String>, String> {
+        let docker = self.client.lock().await;
+
+        let images = docker
+            .list_images(Some(ListImagesOptions::<String> {
+                all: false,
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| format!("Failed to list images: {}", e))?;
+
+        let prefix = format!("{}:", image_name);
+        let mut tags: Vec<String> = images
+            .into_iter()
+            .flat_map(|image| image.repo_tags)
+            .filter_map(|repo_tag| repo_tag.strip_prefix(&prefix).map(String::from))
+            .collect();
+
+        tags.sort();
+        tags.dedup();
+
+        Ok(tags)
+    }
+}
+
+// Parses `docker context ls --format json` output. Newer CLI versions print one
+// JSON object per line, older ones print a single JSON array.
+pub fn parse_context_list(output: &str) -> Result<Vec<DockerContext>, String> {
+    let trimmed = output.trim();
+
+    let raw: Vec<RawDockerContext> = if trimmed.starts_with('[') {
+        serde_json::from_str(trimmed)
+            .map_err(|e| format!("Failed to parse Docker contexts: {}", e))?
+    } else {
+        trimmed
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| format!("Failed to parse Docker contexts: {}", e))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    Ok(raw
+        .into_iter()
+        .map(|c| DockerContext {
+            name: c.name,
+            description: c.description,
+            endpoint: c.docker_endpoint,
+            current: c.current,
+        })
+        .collect())
+}
+
+// Extracts a tar archive into `dest_dir`, rejecting entries that would escape it.
+pub fn extract_tar_archive(archive: &[u8], dest_dir: &Path) -> Result<CopyFromResult, String> {
+    std::fs::create_dir_all(dest_dir)
+        .map_err(|e| format!("Failed to create destination directory: {}", e))?;
+
+    let mut tar = tar::Archive::new(Cursor::new(archive));
+    let entries = tar
+        .entries()
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    let mut files_extracted = 0u32;
+    let mut total_bytes = 0u64;
+
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        let entry_path: PathBuf = entry
+            .path()
+            .map_err(|e| format!("Invalid archive entry path: {}", e))?
+            .into_owned();
+
+        if !is_safe_entry_path(&entry_path) {
+            return Err(format!("Refusing to extract unsafe path: {}", entry_path.display()));
+        }
+
+        let is_file = entry.header().entry_type().is_file();
+        let size = entry.header().size().unwrap_or(0);
+
+        let unpacked = entry
+            .unpack_in(dest_dir)
+            .map_err(|e| format!("Failed to extract {}: {}", entry_path.display(), e))?;
+
+        if unpacked && is_file {
+            files_extracted += 1;
+            total_bytes += size;
+        }
+    }
+
+    Ok(CopyFromResult {
+        files_extracted,
+        total_bytes,
+    })
+}
+
+// Creates a stopped Alpine container with the volume mounted at /volume
+async fn create_volume_helper(
+    docker: &Docker,
+    volume: &str,
+    read_only: bool,
+    cmd: Option<Vec<String>>,
+) -> Result<bollard::models::ContainerCreateResponse, String> {
+    let mut pull = docker.create_image(
+        Some(CreateImageOptions {
+            from_image: VOLUME_BACKUP_IMAGE.to_string(),
+            ..Default::default()
+        }),
+        None,
+        None,
+    );
+    while let Some(result) = pull.next().await {
+        result.map_err(|e| format!("Failed to pull {}: {}", VOLUME_BACKUP_IMAGE, e))?;
+    }
+
+    let mode = if read_only { ":ro" } else { "" };
+    let config = Config {
+        image: Some(VOLUME_BACKUP_IMAGE.to_string()),
+        cmd,
+        host_config: Some(HostConfig {
+            binds: Some(vec![format!("{}:/volume{}", volume, mode)]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    docker
+        .create_container(None::<CreateContainerOptions<String>>, config)
+        .await
+        .map_err(|e| format!("Failed to create volume helper container: {}", e))
+}
+
+async fn remove_volume_helper(docker: &Docker, id: &str) {
+    let _ = docker
+        .remove_container(
+            id,
+            Some(RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            }),
+        )
+        .await;
+}
 
-        let options = ListContainersOptions::<String> {
-            all: true,
-            ..Default::default()
-        };
+// Packages a single file into an in-memory tar archive for upload_to_container.
+pub fn create_tar_archive(file_name: &str, contents: &[u8]) -> Result<Vec<u8>, String> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+
+    let mut builder = tar::Builder::new(Vec::new());
+    builder
+        .append_data(&mut header, file_name, contents)
+        .map_err(|e| format!("Failed to build archive: {}", e))?;
+
+    builder
+        .into_inner()
+        .map_err(|e| format!("Failed to build archive: {}", e))
+}
 
-        let containers = docker
-            .list_containers(Some(options))
+// Packs a file or directory tree into an in-memory tar archive. Entries are
+// named relative to the parent of `host_path`, so the top-level name is kept.
+async fn pack_tar_archive(host_path: &Path) -> Result<(Vec<u8>, CopyToResult), String> {
+    let root_name = host_path
+        .file_name()
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("Invalid path: {}", host_path.display()))?;
+
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut result = CopyToResult {
+        files_copied: 0,
+        total_bytes: 0,
+    };
+    let mut pending = vec![(host_path.to_path_buf(), root_name)];
+
+    while let Some((path, entry_name)) = pending.pop() {
+        let metadata = tokio::fs::metadata(&path)
             .await
-            .map_err(|e| format!("Failed to list containers: {}", e))?;
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
 
-        let container_infos: Vec<ContainerInfo> = containers
-            .into_iter()
-            .filter_map(|c| {
-                let name = c.names
-                    .as_ref()
-                    .and_then(|names| names.first())
-                    .map(|n| n.trim_start_matches('/').to_string())
-                    .unwrap_or_else(|| "unknown".to_string());
+        let mut header = tar::Header::new_gnu();
+        header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
 
-                // Only include signalforge containers
-                if !name.starts_with(SIGNALFORGE_PREFIX) {
-                    return None;
-                }
+        if metadata.is_dir() {
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_size(0);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, &entry_name, std::io::empty())
+                .map_err(|e| format!("Failed to build archive: {}", e))?;
 
-                let ports = c.ports
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|p| PortMapping {
-                        private_port: p.private_port,
-                        public_port: p.public_port,
-                        port_type: p.typ.map(|t| format!("{:?}", t)).unwrap_or_default(),
-                    })
-                    .collect();
+            let mut entries = tokio::fs::read_dir(&path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            {
+                pending.push((entry.path(), entry_name.join(entry.file_name())));
+            }
+        } else if metadata.is_file() {
+            let contents = tokio::fs::read(&path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
 
-                Some(ContainerInfo {
-                    id: c.id.unwrap_or_default(),
-                    name,
-                    image: c.image.unwrap_or_default(),
-                    status: c.status.unwrap_or_default(),
-                    state: c.state.unwrap_or_default(),
-                    created: c.created.unwrap_or(0),
-                    ports,
-                })
-            })
-            .collect();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, &entry_name, contents.as_slice())
+                .map_err(|e| format!("Failed to build archive: {}", e))?;
 
-        Ok(container_infos)
+            result.files_copied += 1;
+            result.total_bytes += contents.len() as u64;
+        }
     }
 
-    pub async fn start_container(&self, id: &str) -> Result<(), String> {
-        let docker = self.client.lock().await;
-        docker
-            .start_container(id, None::<StartContainerOptions<String>>)
-            .await
-            .map_err(|e| format!("Failed to start container: {}", e))
-    }
+    let archive = builder
+        .into_inner()
+        .map_err(|e| format!("Failed to build archive: {}", e))?;
 
-    pub async fn stop_container(&self, id: &str) -> Result<(), String> {
-        let docker = self.client.lock().await;
-        docker
-            .stop_container(id, Some(StopContainerOptions { t: 10 }))
-            .await
-            .map_err(|e| format!("Failed to stop container: {}", e))
-    }
+    Ok((archive, result))
+}
 
-    pub async fn restart_container(&self, id: &str) -> Result<(), String> {
-        let docker = self.client.lock().await;
-        docker
-            .restart_container(id, Some(RestartContainerOptions { t: 10 }))
-            .await
-            .map_err(|e| format!("Failed to restart container: {}", e))
-    }
+fn is_safe_entry_path(path: &Path) -> bool {
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
 
-    pub async fn get_container_logs(&self, id: &str, tail: Option<u64>) -> Result<Vec<String>, String> {
-        let docker = self.client.lock().await;
+fn inspect_context(name: &str) -> Result<RawContextInspect, String> {
+    let output = Command::new("docker")
+        .args(["context", "inspect", name])
+        .output()
+        .map_err(|e| format!("Failed to inspect Docker context: {}", e))?;
 
-        let options = LogsOptions::<String> {
-            stdout: true,
-            stderr: true,
-            tail: tail.map(|t| t.to_string()).unwrap_or_else(|| "100".to_string()),
-            timestamps: true,
-            ..Default::default()
-        };
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
 
-        let mut stream = docker.logs(id, Some(options));
-        let mut logs = Vec::new();
+    let contexts: Vec<RawContextInspect> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse Docker context: {}", e))?;
+    contexts
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Docker context not found: {}", name))
+}
 
-        while let Some(result) = stream.next().await {
-            match result {
-                Ok(output) => {
-                    let line = output.to_string();
-                    logs.push(line);
-                }
-                Err(e) => {
-                    return Err(format!("Failed to get logs: {}", e));
-                }
+// Connects the way the docker CLI would for this context. Endpoints we can't
+// reach the same way are an error rather than a silent fallback to the local
+// daemon, which would put the app and the CLI on different hosts.
+fn connect_context(name: &str) -> Result<Docker, String> {
+    let context = inspect_context(name)?;
+    let endpoint = context
+        .endpoints
+        .get("docker")
+        .ok_or_else(|| format!("Docker context {} has no docker endpoint", name))?;
+    let host = endpoint.host.as_str();
+
+    let docker = if host.starts_with("unix://") || host.starts_with("npipe://") {
+        Docker::connect_with_local(host, CONNECT_TIMEOUT, API_DEFAULT_VERSION)
+    } else if host.starts_with("tcp://") || host.starts_with("http://") || host.starts_with("https://") {
+        let material = context.tls_material.get("docker").cloned().unwrap_or_default();
+        if material.is_empty() {
+            if endpoint.skip_tls_verify || host.starts_with("https://") {
+                return Err(format!(
+                    "Docker context {} uses TLS without client certificates, which is not supported",
+                    name
+                ));
             }
+            Docker::connect_with_http(host, CONNECT_TIMEOUT, API_DEFAULT_VERSION)
+        } else {
+            if endpoint.skip_tls_verify {
+                return Err(format!(
+                    "Docker context {} skips TLS verification, which is not supported",
+                    name
+                ));
+            }
+            let tls_dir = Path::new(&context.storage.tls_path).join("docker");
+            let (ca, cert, key) = (tls_dir.join("ca.pem"), tls_dir.join("cert.pem"), tls_dir.join("key.pem"));
+            if !(ca.exists() && cert.exists() && key.exists()) {
+                return Err(format!(
+                    "Docker context {} needs ca.pem, cert.pem and key.pem for TLS",
+                    name
+                ));
+            }
+            Docker::connect_with_ssl(host, &key, &cert, &ca, CONNECT_TIMEOUT, API_DEFAULT_VERSION)
         }
+    } else if host.starts_with("ssh://") {
+        return Err(format!(
+            "Docker context {} connects over SSH, which is not supported; expose the daemon over TLS instead",
+            name
+        ));
+    } else {
+        return Err(format!("Unsupported endpoint for Docker context {}: {}", name, host));
+    };
 
-        Ok(logs)
+    docker.map_err(|e| format!("Failed to connect to Docker: {}", e))
+}
+
+// Accepts "KILL", "sigkill" or "SIGKILL"; an empty string means SIGKILL
+pub fn normalize_signal(signal: &str) -> Result<String, String> {
+    let signal = signal.trim().to_uppercase();
+    if signal.is_empty() {
+        return Ok("SIGKILL".to_string());
     }
 
-    pub async fn get_container_stats(&self, id: &str) -> Result<ContainerStats, String> {
-        let docker = self.client.lock().await;
+    let signal = if signal.starts_with("SIG") { signal } else { format!("SIG{}", signal) };
+    if KILL_SIGNALS.contains(&signal.as_str()) {
+        Ok(signal)
+    } else {
+        Err(format!(
+            "Unsupported signal: {}. Expected one of: {}",
+            signal,
+            KILL_SIGNALS.join(", ")
+        ))
+    }
+}
 
-        let options = StatsOptions {
-            stream: false,
-            one_shot: true,
-        };
+// Docker reports -1 for sizes it hasn't computed
+fn sum_sizes(sizes: impl Iterator<Item = Option<i64>>) -> u64 {
+    sizes.flatten().map(|size| size.max(0) as u64).sum()
+}
 
-        let mut stream = docker.stats(id, Some(options));
+// Parses an IPv4 CIDR such as "172.25.0.0/16" into (network, prefix length)
+fn parse_ipv4_cidr(cidr: &str) -> Option<(u32, u32)> {
+    let (addr, prefix) = cidr.trim().split_once('/')?;
+    let addr: std::net::Ipv4Addr = addr.parse().ok()?;
+    let prefix: u32 = prefix.parse().ok().filter(|p| *p <= 32)?;
+    Some((u32::from(addr), prefix))
+}
 
-        if let Some(result) = stream.next().await {
-            let stats = result.map_err(|e| format!("Failed to get stats: {}", e))?;
-            return Ok(calculate_stats(&stats));
+// True when two IPv4 CIDR ranges share any address. Anything that isn't
+// IPv4 CIDR notation is treated as not overlapping.
+pub fn subnets_overlap(a: &str, b: &str) -> bool {
+    match (parse_ipv4_cidr(a), parse_ipv4_cidr(b)) {
+        (Some((a_addr, a_prefix)), Some((b_addr, b_prefix))) => {
+            let prefix = a_prefix.min(b_prefix);
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            a_addr & mask == b_addr & mask
         }
+        _ => false,
+    }
+}
 
-        Err("No stats available".to_string())
+// The registry host an image reference points at, "docker.io" when it has
+// none. The first path segment is a host only if it looks like one.
+pub fn image_registry_host(name: &str) -> String {
+    match name.split_once('/') {
+        Some((first, _))
+            if first.contains('.') || first.contains(':') || first == "localhost" =>
+        {
+            first.to_lowercase()
+        }
+        _ => "docker.io".to_string(),
     }
+}
 
-    pub async fn get_docker_info(&self) -> Result<DockerInfo, String> {
-        let docker = self.client.lock().await;
+// Splits "repo:tag" for the pull API, which pulls every tag when none is
+// given. Registry ports ("host:5000/repo") and digests are left intact.
+fn split_image_reference(name: &str) -> (String, String) {
+    if name.contains('@') {
+        return (name.to_string(), String::new());
+    }
 
-        let info = docker
-            .info()
-            .await
-            .map_err(|e| format!("Failed to get Docker info: {}", e))?;
+    let name_start = name.rfind('/').map(|i| i + 1).unwrap_or(0);
+    match name[name_start..].rfind(':') {
+        Some(i) => (
+            name[..name_start + i].to_string(),
+            name[name_start + i + 1..].to_string(),
+        ),
+        None => (name.to_string(), "latest".to_string()),
+    }
+}
 
-        Ok(DockerInfo {
-            containers_running: info.containers_running.unwrap_or(0) as i64,
-            containers_paused: info.containers_paused.unwrap_or(0) as i64,
-            containers_stopped: info.containers_stopped.unwrap_or(0) as i64,
-            images: info.images.unwrap_or(0) as i64,
-            docker_version: info.server_version.unwrap_or_default(),
-            os_type: info.os_type.unwrap_or_default(),
-            architecture: info.architecture.unwrap_or_default(),
-            memory_total: info.mem_total.unwrap_or(0),
-            cpus: info.ncpu.unwrap_or(0) as i64,
-        })
+fn validate_image_reference(repository: &str, tag: &str) -> Result<(), String> {
+    if repository.is_empty() {
+        return Err("Repository must not be empty".to_string());
+    }
+
+    if repository.chars().any(|c| c.is_uppercase()) {
+        return Err(format!("Repository must be lowercase: {}", repository));
+    }
+
+    if tag.is_empty() {
+        return Err("Tag must not be empty".to_string());
+    }
+
+    if tag.chars().any(char::is_whitespace) {
+        return Err(format!("Tag must not contain spaces: {}", tag));
     }
+
+    Ok(())
 }
 
 fn calculate_stats(stats: &Stats) -> ContainerStats {
@@ -292,6 +1716,17 @@ pub struct NetworkTopology {
     pub connections: Vec<NetworkConnection>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContainerNetworkInterface {
+    pub network_name: String,
+    pub ip_address: String,
+    pub ipv6_address: Option<String>,
+    pub mac_address: String,
+    pub gateway: String,
+    pub network_id: String,
+    pub aliases: Vec<String>,
+}
+
 impl DockerClient {
     pub async fn get_network_topology(&self) -> Result<NetworkTopology, String> {
         let docker = self.client.lock().await;
@@ -388,10 +1823,6 @@ impl DockerClient {
                 "other"
             }.to_string();
 
-            // Mock CPU/mem - real stats would require streaming API
-            let cpu = (container_id.chars().map(|c| c as u32).sum::<u32>() % 20) as f64;
-            let mem = (container_id.chars().map(|c| c as u32).sum::<u32>() % 2048) as u64;
-
             for network in &networks {
                 network_map
                     .entry(network.clone())
@@ -407,11 +1838,30 @@ impl DockerClient {
                 ip,
                 ports,
                 health,
-                cpu,
-                mem,
+                cpu: 0.0,
+                mem: 0,
             });
         }
 
+        // One stats round-trip per container, run concurrently
+        let stats = join_all(containers.iter().map(|c| {
+            let mut stream = docker.stats(
+                &c.id,
+                Some(StatsOptions {
+                    stream: false,
+                    one_shot: true,
+                }),
+            );
+            async move { stream.next().await.and_then(|r| r.ok()) }
+        }))
+        .await;
+
+        for (container, stats) in containers.iter_mut().zip(stats) {
+            if let Some(stats) = stats {
+                apply_usage(container, &stats);
+            }
+        }
+
         let connections = infer_connections(&containers, &network_map);
 
         Ok(NetworkTopology {
@@ -419,6 +1869,128 @@ impl DockerClient {
             connections,
         })
     }
+
+    pub async fn get_disk_usage(&self) -> Result<DockerDiskUsage, String> {
+        let docker = self.client.lock().await;
+
+        let usage = docker
+            .df()
+            .await
+            .map_err(|e| format!("Failed to get disk usage: {}", e))?;
+
+        Ok(DockerDiskUsage {
+            images_size: sum_sizes(usage.images.iter().flatten().map(|i| Some(i.size))),
+            containers_size: sum_sizes(usage.containers.iter().flatten().map(|c| c.size_rw)),
+            volumes_size: sum_sizes(
+                usage
+                    .volumes
+                    .iter()
+                    .flatten()
+                    .map(|v| v.usage_data.as_ref().map(|u| u.size)),
+            ),
+            build_cache_size: sum_sizes(usage.build_cache.iter().flatten().map(|b| b.size)),
+        })
+    }
+
+    // Removes stopped containers, dangling images and unused networks, plus
+    // unused volumes when `volumes` is set
+    pub async fn system_prune(&self, volumes: bool) -> Result<PruneResult, String> {
+        let docker = self.client.lock().await;
+
+        let containers = docker
+            .prune_containers(None::<PruneContainersOptions<String>>)
+            .await
+            .map_err(|e| format!("Failed to prune containers: {}", e))?;
+        let images = docker
+            .prune_images(None::<PruneImagesOptions<String>>)
+            .await
+            .map_err(|e| format!("Failed to prune images: {}", e))?;
+        docker
+            .prune_networks(None::<PruneNetworksOptions<String>>)
+            .await
+            .map_err(|e| format!("Failed to prune networks: {}", e))?;
+
+        let mut space_reclaimed =
+            sum_sizes([containers.space_reclaimed, images.space_reclaimed].into_iter());
+
+        if volumes {
+            let pruned = docker
+                .prune_volumes(None::<PruneVolumesOptions<String>>)
+                .await
+                .map_err(|e| format!("Failed to prune volumes: {}", e))?;
+            space_reclaimed += sum_sizes(std::iter::once(pruned.space_reclaimed));
+        }
+
+        Ok(PruneResult { space_reclaimed })
+    }
+
+    pub async fn get_container_labels(&self, id: String) -> Result<HashMap<String, String>, String> {
+        let docker = self.client.lock().await;
+
+        let inspect = docker
+            .inspect_container(&id, None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| format!("Failed to inspect container: {}", e))?;
+
+        Ok(inspect
+            .config
+            .and_then(|config| config.labels)
+            .unwrap_or_default())
+    }
+
+    pub async fn get_container_network_settings(
+        &self,
+        id: &str,
+    ) -> Result<Vec<ContainerNetworkInterface>, String> {
+        let docker = self.client.lock().await;
+
+        let inspect = docker
+            .inspect_container(id, None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| format!("Failed to inspect container: {}", e))?;
+
+        let mut interfaces: Vec<ContainerNetworkInterface> = inspect
+            .network_settings
+            .and_then(|ns| ns.networks)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(network_name, endpoint)| ContainerNetworkInterface {
+                network_name,
+                ip_address: endpoint.ip_address.unwrap_or_default(),
+                ipv6_address: endpoint.global_ipv6_address.filter(|ip| !ip.is_empty()),
+                mac_address: endpoint.mac_address.unwrap_or_default(),
+                gateway: endpoint.gateway.unwrap_or_default(),
+                network_id: endpoint.network_id.unwrap_or_default(),
+                aliases: endpoint.aliases.unwrap_or_default(),
+            })
+            .collect();
+
+        interfaces.sort_by(|a, b| a.network_name.cmp(&b.network_name));
+
+        Ok(interfaces)
+    }
+}
+
+// CPU as a percentage and memory in MiB, from a one-shot stats sample
+fn apply_usage(container: &mut NetworkContainer, stats: &Stats) {
+    let stats = calculate_stats(stats);
+    container.cpu = stats.cpu_percent;
+    container.mem = stats.memory_usage / (1024 * 1024);
+}
+
+// Docker reports "no limit" as 0, or -1 for swap and pids
+fn limit_value(value: Option<i64>) -> Option<i64> {
+    value.filter(|v| *v > 0)
+}
+
+fn resource_limits(host_config: &HostConfig) -> ResourceLimits {
+    ResourceLimits {
+        memory_bytes: limit_value(host_config.memory),
+        memory_swap_bytes: limit_value(host_config.memory_swap),
+        cpu_quota: limit_value(host_config.cpu_quota),
+        cpu_period: limit_value(host_config.cpu_period),
+        pids_limit: limit_value(host_config.pids_limit),
+    }
 }
 
 fn infer_connections(
@@ -491,3 +2063,274 @@ fn infer_connections(
 
     connections
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_context_list_reads_json_lines() {
+        let output = r#"{"Current":true,"Description":"Current DOCKER_HOST based configuration","DockerEndpoint":"unix:///var/run/docker.sock","Error":"","Name":"default"}
+{"Current":false,"Description":"Build box","DockerEndpoint":"tcp://10.0.0.5:2376","Error":"","Name":"remote"}
+"#;
+
+        let contexts = parse_context_list(output).unwrap();
+
+        assert_eq!(contexts.len(), 2);
+        assert_eq!(contexts[0].name, "default");
+        assert_eq!(contexts[0].endpoint, "unix:///var/run/docker.sock");
+        assert!(contexts[0].current);
+        assert_eq!(contexts[1].name, "remote");
+        assert_eq!(contexts[1].description, "Build box");
+        assert_eq!(contexts[1].endpoint, "tcp://10.0.0.5:2376");
+        assert!(!contexts[1].current);
+    }
+
+    #[test]
+    fn parse_context_list_reads_json_array() {
+        let output = r#"[{"Current":false,"Description":"","DockerEndpoint":"unix:///var/run/docker.sock","Name":"default"},{"Current":true,"Description":"","DockerEndpoint":"unix:///home/dev/.colima/default/docker.sock","Name":"colima"}]"#;
+
+        let contexts = parse_context_list(output).unwrap();
+
+        assert_eq!(contexts.len(), 2);
+        assert_eq!(contexts[1].name, "colima");
+        assert!(contexts[1].current);
+    }
+
+    #[test]
+    fn parse_context_list_rejects_invalid_output() {
+        assert!(parse_context_list("not json").is_err());
+        assert!(parse_context_list("").unwrap().is_empty());
+    }
+
+    fn tar_stream(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, data) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn extract_tar_archive_unpacks_files() {
+        let archive = tar_stream(&[
+            ("app/index.php", b"<?php echo 1;"),
+            ("app/logs/error.log", b"boom\n"),
+        ]);
+        let dest = tempfile::tempdir().unwrap();
+
+        let result = extract_tar_archive(&archive, dest.path()).unwrap();
+
+        assert_eq!(result.files_extracted, 2);
+        assert_eq!(result.total_bytes, 18);
+        assert_eq!(std::fs::read(dest.path().join("app/index.php")).unwrap(), b"<?php echo 1;");
+        assert_eq!(std::fs::read(dest.path().join("app/logs/error.log")).unwrap(), b"boom\n");
+    }
+
+    #[test]
+    fn extract_tar_archive_rejects_parent_paths() {
+        // tar::Builder refuses to write "..", so set the raw name bytes
+        let mut header = tar::Header::new_old();
+        let name = b"../escape.txt";
+        header.as_old_mut().name[..name.len()].copy_from_slice(name);
+        header.set_size(4);
+        header.set_mode(0o644);
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_cksum();
+
+        let mut archive = header.as_bytes().to_vec();
+        archive.extend_from_slice(b"evil");
+        archive.resize(archive.len() + 508 + 1024, 0);
+
+        let parent = tempfile::tempdir().unwrap();
+        let dest = parent.path().join("out");
+
+        assert!(extract_tar_archive(&archive, &dest).is_err());
+        assert!(!parent.path().join("escape.txt").exists());
+    }
+
+    // Points at a port nothing listens on; bollard only connects on request
+    fn unreachable_client() -> DockerClient {
+        let docker = Docker::connect_with_http("http://127.0.0.1:9", CONNECT_TIMEOUT, API_DEFAULT_VERSION)
+            .unwrap();
+        DockerClient {
+            client: Arc::new(Mutex::new(docker)),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_container_resources_requires_a_parameter() {
+        let client = unreachable_client();
+
+        let result = client.update_container_resources("abc123", None, None, None).await;
+
+        assert_eq!(result, Err("no update parameters specified".to_string()));
+    }
+
+    #[tokio::test]
+    async fn update_container_resources_rejects_tiny_memory_limit() {
+        let client = unreachable_client();
+
+        let result = client.update_container_resources("abc123", Some(1024), None, None).await;
+
+        assert!(result.unwrap_err().contains("at least"));
+    }
+
+    #[test]
+    fn unset_limits_map_to_none() {
+        let unlimited = HostConfig {
+            memory: Some(0),
+            memory_swap: Some(-1),
+            cpu_quota: Some(0),
+            cpu_period: None,
+            pids_limit: Some(-1),
+            ..Default::default()
+        };
+
+        let limits = resource_limits(&unlimited);
+
+        assert_eq!(limits.memory_bytes, None);
+        assert_eq!(limits.memory_swap_bytes, None);
+        assert_eq!(limits.cpu_quota, None);
+        assert_eq!(limits.cpu_period, None);
+        assert_eq!(limits.pids_limit, None);
+    }
+
+    #[test]
+    fn configured_limits_are_kept() {
+        let limited = HostConfig {
+            memory: Some(512 * 1024 * 1024),
+            memory_swap: Some(1024 * 1024 * 1024),
+            cpu_quota: Some(50_000),
+            cpu_period: Some(100_000),
+            pids_limit: Some(200),
+            ..Default::default()
+        };
+
+        let limits = resource_limits(&limited);
+
+        assert_eq!(limits.memory_bytes, Some(536_870_912));
+        assert_eq!(limits.memory_swap_bytes, Some(1_073_741_824));
+        assert_eq!(limits.cpu_quota, Some(50_000));
+        assert_eq!(limits.cpu_period, Some(100_000));
+        assert_eq!(limits.pids_limit, Some(200));
+    }
+
+    #[tokio::test]
+    async fn single_file_is_packed_as_one_tar_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("hello.txt");
+        std::fs::write(&file, "hello world\n").unwrap();
+
+        let (archive, result) = pack_tar_archive(&file).await.unwrap();
+
+        assert_eq!(result.files_copied, 1);
+        assert_eq!(result.total_bytes, 12);
+        // One header block, one padded data block, two zero end blocks
+        assert_eq!(archive.len(), 4 * 512);
+        assert_eq!(&archive[..10], b"hello.txt\0");
+        assert_eq!(&archive[100..107], b"0000644");
+        assert_eq!(&archive[124..135], b"00000000014");
+        assert!(tar::EntryType::new(archive[156]).is_file());
+        assert_eq!(&archive[257..263], b"ustar ");
+        assert_eq!(&archive[512..524], b"hello world\n");
+        assert!(archive[524..].iter().all(|b| *b == 0));
+    }
+
+    #[tokio::test]
+    async fn directory_is_packed_under_its_own_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let public = dir.path().join("public");
+        std::fs::create_dir_all(public.join("css")).unwrap();
+        std::fs::write(public.join("index.php"), "<?php").unwrap();
+        std::fs::write(public.join("css/app.css"), "body{}").unwrap();
+
+        let (archive, result) = pack_tar_archive(&public).await.unwrap();
+
+        let mut entries: Vec<(String, tar::EntryType)> = tar::Archive::new(archive.as_slice())
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                (entry.path().unwrap().to_string_lossy().to_string(), entry.header().entry_type())
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            entries,
+            vec![
+                ("public".to_string(), tar::EntryType::Directory),
+                ("public/css".to_string(), tar::EntryType::Directory),
+                ("public/css/app.css".to_string(), tar::EntryType::Regular),
+                ("public/index.php".to_string(), tar::EntryType::Regular),
+            ]
+        );
+        assert_eq!(result.files_copied, 2);
+        assert_eq!(result.total_bytes, 11);
+    }
+
+    #[test]
+    fn topology_usage_comes_from_container_stats() {
+        let mut container = NetworkContainer {
+            id: "3f2a9c1be4d7".to_string(),
+            name: "shop-php-1".to_string(),
+            container_type: "app".to_string(),
+            networks: vec!["shop_default".to_string()],
+            ip: "172.26.0.3".to_string(),
+            ports: String::new(),
+            health: "healthy".to_string(),
+            cpu: 0.0,
+            mem: 0,
+        };
+        // 0.1s of CPU over 1s of system time on 2 CPUs, 256 MiB in use
+        let stats: Stats = serde_json::from_value(serde_json::json!({
+            "read": "2024-05-01T12:00:01Z",
+            "preread": "2024-05-01T12:00:00Z",
+            "num_procs": 0,
+            "pids_stats": {},
+            "memory_stats": { "usage": 268_435_456u64, "limit": 2_147_483_648u64 },
+            "blkio_stats": {},
+            "storage_stats": {},
+            "cpu_stats": {
+                "cpu_usage": { "total_usage": 600_000_000u64, "usage_in_usermode": 0, "usage_in_kernelmode": 0 },
+                "system_cpu_usage": 11_000_000_000u64,
+                "online_cpus": 2,
+                "throttling_data": { "periods": 0, "throttled_periods": 0, "throttled_time": 0 }
+            },
+            "precpu_stats": {
+                "cpu_usage": { "total_usage": 500_000_000u64, "usage_in_usermode": 0, "usage_in_kernelmode": 0 },
+                "system_cpu_usage": 10_000_000_000u64,
+                "online_cpus": 2,
+                "throttling_data": { "periods": 0, "throttled_periods": 0, "throttled_time": 0 }
+            }
+        }))
+        .unwrap();
+
+        apply_usage(&mut container, &stats);
+
+        assert!((container.cpu - 20.0).abs() < 1e-9);
+        assert_eq!(container.mem, 256);
+    }
+
+    #[test]
+    fn normalize_signal_accepts_names_with_or_without_prefix() {
+        assert_eq!(normalize_signal("").unwrap(), "SIGKILL");
+        assert_eq!(normalize_signal("  ").unwrap(), "SIGKILL");
+        assert_eq!(normalize_signal("sigterm").unwrap(), "SIGTERM");
+        assert_eq!(normalize_signal("usr1").unwrap(), "SIGUSR1");
+        assert_eq!(normalize_signal(" SIGHUP ").unwrap(), "SIGHUP");
+    }
+
+    #[test]
+    fn normalize_signal_rejects_unknown_names() {
+        let err = normalize_signal("SIGFOO").unwrap_err();
+
+        assert!(err.starts_with("Unsupported signal: SIGFOO. Expected one of: "));
+        assert!(err.contains("SIGKILL"));
+        assert!(normalize_signal("9").is_err());
+    }
+}
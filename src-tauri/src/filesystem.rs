@@ -1,8 +1,28 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use crate::commands::AppState;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::{Instant, SystemTime};
+use tauri::State;
 use walkdir::WalkDir;
 
+// Names and types of a directory's entries as of its mtime. Sizes and
+// modification times aren't cached: editing a file doesn't touch its
+// directory's mtime, so they are read fresh on every listing.
+pub struct CachedListing {
+    mtime: SystemTime,
+    cached_at: Instant,
+    entries: Vec<(String, bool)>,
+}
+
+pub type DirectoryCache = HashMap<PathBuf, CachedListing>;
+
+// Oldest listing is evicted once this many directories are cached
+const DIRECTORY_CACHE_LIMIT: usize = 256;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DirectoryEntry {
     pub name: String,
@@ -12,15 +32,144 @@ pub struct DirectoryEntry {
     pub modified: i64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExtendedMetadata {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: i64,
+    pub xattrs: HashMap<String, String>,
+    pub creation_time: Option<i64>,
+    pub inode: Option<u64>,
+    pub hard_links: u32,
+    pub device_id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileUsageInfo {
+    pub in_use: bool,
+    pub processes: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FileContent {
     pub path: String,
     pub content: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiffLine {
+    pub kind: String,
+    pub content: String,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileDiff {
+    pub path: String,
+    pub additions: u32,
+    pub deletions: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileChunk {
+    pub content: String,
+    pub offset: u64,
+    pub bytes_read: u64,
+    pub total_size: u64,
+    pub has_more: bool,
+}
+
+// Line-based diff of two texts using the longest common subsequence.
+// Returns None when both texts are identical.
+pub fn diff_contents(path: &str, old: &str, new: &str) -> Option<FileDiff> {
+    if old == new {
+        return None;
+    }
+
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let (n, m) = (old_lines.len(), new_lines.len());
+
+    // lcs[i][j] = LCS length of old_lines[i..] and new_lines[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_lines[i] == new_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut additions, mut deletions) = (0u32, 0u32);
+    let (mut i, mut j) = (0, 0);
+
+    while i < n || j < m {
+        if i < n && j < m && old_lines[i] == new_lines[j] {
+            lines.push(DiffLine {
+                kind: "unchanged".to_string(),
+                content: old_lines[i].to_string(),
+                old_line: Some(i + 1),
+                new_line: Some(j + 1),
+            });
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            lines.push(DiffLine {
+                kind: "added".to_string(),
+                content: new_lines[j].to_string(),
+                old_line: None,
+                new_line: Some(j + 1),
+            });
+            additions += 1;
+            j += 1;
+        } else {
+            lines.push(DiffLine {
+                kind: "removed".to_string(),
+                content: old_lines[i].to_string(),
+                old_line: Some(i + 1),
+                new_line: None,
+            });
+            deletions += 1;
+            i += 1;
+        }
+    }
+
+    Some(FileDiff {
+        path: path.to_string(),
+        additions,
+        deletions,
+        lines,
+    })
+}
+
+// Drops cached listings for `path`, anything below it, and its parent
+async fn invalidate_directory_cache(state: &AppState, path: &Path) {
+    let mut cache = state.directory_cache.lock().await;
+    cache.retain(|dir, _| !dir.starts_with(path));
+    if let Some(parent) = path.parent() {
+        cache.remove(parent);
+    }
+}
+
 #[tauri::command]
-pub async fn list_directory(path: String) -> Result<Vec<DirectoryEntry>, String> {
-    let path = PathBuf::from(&path);
+pub async fn clear_directory_cache(state: State<'_, AppState>) -> Result<(), String> {
+    state.directory_cache.lock().await.clear();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_directory(path: String, state: State<'_, AppState>) -> Result<Vec<DirectoryEntry>, String> {
+    list_directory_cached(&state, PathBuf::from(&path)).await
+}
+
+async fn list_directory_cached(state: &AppState, path: PathBuf) -> Result<Vec<DirectoryEntry>, String> {
 
     if !path.exists() {
         return Err(format!("Path does not exist: {}", path.display()));
@@ -30,14 +179,64 @@ pub async fn list_directory(path: String) -> Result<Vec<DirectoryEntry>, String>
         return Err(format!("Path is not a directory: {}", path.display()));
     }
 
-    let mut entries = Vec::new();
+    // A directory's mtime changes whenever an entry is added, removed or
+    // renamed, so a matching mtime means the cached names are current
+    let mtime = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("Failed to read metadata: {}", e))?;
+
+    let cached = state
+        .directory_cache
+        .lock()
+        .await
+        .get(&path)
+        .filter(|listing| listing.mtime == mtime)
+        .map(|listing| listing.entries.clone());
+
+    let names = match cached {
+        Some(names) => {
+            state.cache_hit_count.fetch_add(1, Ordering::Relaxed);
+            names
+        }
+        None => {
+            let read_dir = fs::read_dir(&path)
+                .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+            let mut names = Vec::new();
+            for entry in read_dir {
+                let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+                let file_type = entry.file_type().map_err(|e| format!("Failed to read metadata: {}", e))?;
+                names.push((entry.file_name().to_string_lossy().to_string(), file_type.is_dir()));
+            }
+
+            let mut cache = state.directory_cache.lock().await;
+            if cache.len() >= DIRECTORY_CACHE_LIMIT && !cache.contains_key(&path) {
+                let oldest = cache
+                    .iter()
+                    .min_by_key(|(_, listing)| listing.cached_at)
+                    .map(|(dir, _)| dir.clone());
+                if let Some(oldest) = oldest {
+                    cache.remove(&oldest);
+                }
+            }
+            cache.insert(
+                path.clone(),
+                CachedListing {
+                    mtime,
+                    cached_at: Instant::now(),
+                    entries: names.clone(),
+                },
+            );
+
+            names
+        }
+    };
 
-    let read_dir = fs::read_dir(&path)
-        .map_err(|e| format!("Failed to read directory: {}", e))?;
+    let mut entries = Vec::with_capacity(names.len());
 
-    for entry in read_dir {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let metadata = entry.metadata().map_err(|e| format!("Failed to read metadata: {}", e))?;
+    for (name, is_dir) in names {
+        let entry_path = path.join(&name);
+        let metadata = fs::symlink_metadata(&entry_path).map_err(|e| format!("Failed to read metadata: {}", e))?;
 
         let modified = metadata
             .modified()
@@ -45,9 +244,9 @@ pub async fn list_directory(path: String) -> Result<Vec<DirectoryEntry>, String>
             .unwrap_or(0);
 
         entries.push(DirectoryEntry {
-            name: entry.file_name().to_string_lossy().to_string(),
-            path: entry.path().to_string_lossy().to_string(),
-            is_dir: metadata.is_dir(),
+            name,
+            path: entry_path.to_string_lossy().to_string(),
+            is_dir,
             size: metadata.len(),
             modified,
         });
@@ -100,6 +299,151 @@ pub async fn list_directory_recursive(path: String, max_depth: Option<usize>) ->
     Ok(entries)
 }
 
+#[cfg(unix)]
+fn read_xattrs(path: &Path) -> HashMap<String, String> {
+    let Ok(names) = xattr::list(path) else {
+        return HashMap::new();
+    };
+
+    names
+        .filter_map(|name| {
+            let value = xattr::get(path, &name).ok().flatten()?;
+            Some((
+                name.to_string_lossy().to_string(),
+                String::from_utf8_lossy(&value).to_string(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn read_xattrs(_path: &Path) -> HashMap<String, String> {
+    HashMap::new()
+}
+
+#[tauri::command]
+pub async fn get_file_metadata(path: String) -> Result<ExtendedMetadata, String> {
+    let path_buf = PathBuf::from(&path);
+
+    if !path_buf.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+
+    let metadata = fs::metadata(&path_buf)
+        .map_err(|e| format!("Failed to read metadata: {}", e))?;
+
+    let to_timestamp = |t: std::time::SystemTime| {
+        t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+    };
+
+    #[cfg(unix)]
+    let (inode, hard_links, device_id) = {
+        use std::os::unix::fs::MetadataExt;
+        (Some(metadata.ino()), metadata.nlink() as u32, metadata.dev())
+    };
+
+    #[cfg(not(unix))]
+    let (inode, hard_links, device_id) = (None, 1, 0);
+
+    Ok(ExtendedMetadata {
+        name: path_buf
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone()),
+        path: path.clone(),
+        is_dir: metadata.is_dir(),
+        size: metadata.len(),
+        modified: metadata.modified().map(to_timestamp).unwrap_or(0),
+        xattrs: read_xattrs(&path_buf),
+        creation_time: metadata.created().ok().map(to_timestamp),
+        inode,
+        hard_links,
+        device_id,
+    })
+}
+
+// Scans /proc/<pid>/fd for descriptors pointing at `target`. Processes we are
+// not allowed to inspect are skipped.
+#[cfg(target_os = "linux")]
+fn find_processes_using(target: &Path) -> Vec<String> {
+    let Ok(proc_entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    let mut processes = Vec::new();
+
+    for entry in proc_entries.filter_map(|e| e.ok()) {
+        let pid = entry.file_name().to_string_lossy().to_string();
+        if !pid.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let has_open_fd = fds
+            .filter_map(|fd| fd.ok())
+            .filter_map(|fd| fs::read_link(fd.path()).ok())
+            .any(|link| link == target);
+
+        if has_open_fd {
+            let name = fs::read_to_string(entry.path().join("comm"))
+                .map(|c| c.trim().to_string())
+                .unwrap_or_else(|_| "unknown".to_string());
+            processes.push(format!("{} ({})", name, pid));
+        }
+    }
+
+    processes
+}
+
+// Parses `lsof <path>` output: a header line followed by COMMAND PID ... rows
+#[cfg(target_os = "macos")]
+fn find_processes_using(target: &Path) -> Vec<String> {
+    let Ok(output) = std::process::Command::new("lsof").arg(target).output() else {
+        return Vec::new();
+    };
+
+    let mut processes: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let command = parts.next()?;
+            let pid = parts.next()?;
+            Some(format!("{} ({})", command, pid))
+        })
+        .collect();
+
+    processes.dedup();
+    processes
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn find_processes_using(_target: &Path) -> Vec<String> {
+    Vec::new()
+}
+
+#[tauri::command]
+pub async fn is_file_in_use(path: String) -> Result<FileUsageInfo, String> {
+    let path_buf = PathBuf::from(&path);
+
+    if !path_buf.exists() {
+        return Err(format!("File does not exist: {}", path));
+    }
+
+    let target = fs::canonicalize(&path_buf)
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+
+    let processes = find_processes_using(&target);
+
+    Ok(FileUsageInfo {
+        in_use: !processes.is_empty(),
+        processes,
+    })
+}
+
 #[tauri::command]
 pub async fn read_file(path: String) -> Result<FileContent, String> {
     let path_buf = PathBuf::from(&path);
@@ -119,9 +463,77 @@ pub async fn read_file(path: String) -> Result<FileContent, String> {
 }
 
 #[tauri::command]
-pub async fn write_file(path: String, content: String) -> Result<(), String> {
+pub async fn read_file_range(
+    path: String,
+    offset_bytes: u64,
+    length_bytes: u64,
+) -> Result<FileChunk, String> {
     let path_buf = PathBuf::from(&path);
 
+    if !path_buf.is_file() {
+        return Err(format!("Path is not a file: {}", path));
+    }
+
+    let mut file = fs::File::open(&path_buf)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    let total_size = file
+        .metadata()
+        .map_err(|e| format!("Failed to read metadata: {}", e))?
+        .len();
+
+    let offset = offset_bytes.min(total_size);
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to seek file: {}", e))?;
+
+    let mut buffer = Vec::new();
+    file.take(length_bytes)
+        .read_to_end(&mut buffer)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let bytes_read = buffer.len() as u64;
+
+    Ok(FileChunk {
+        content: String::from_utf8_lossy(&buffer).to_string(),
+        offset,
+        bytes_read,
+        total_size,
+        has_more: offset + bytes_read < total_size,
+    })
+}
+
+#[tauri::command]
+pub async fn read_file_lines(path: String, start_line: usize, count: usize) -> Result<Vec<String>, String> {
+    let path_buf = PathBuf::from(&path);
+
+    if !path_buf.is_file() {
+        return Err(format!("Path is not a file: {}", path));
+    }
+
+    let file = fs::File::open(&path_buf)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    // Read raw lines so invalid UTF-8 is replaced instead of aborting the read
+    BufReader::new(file)
+        .split(b'\n')
+        .skip(start_line)
+        .take(count)
+        .map(|line| {
+            line.map(|bytes| {
+                String::from_utf8_lossy(&bytes)
+                    .trim_end_matches('\r')
+                    .to_string()
+            })
+            .map_err(|e| format!("Failed to read file: {}", e))
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn write_file(path: String, content: String, state: State<'_, AppState>) -> Result<(), String> {
+    let path_buf = PathBuf::from(&path);
+    invalidate_directory_cache(&state, &path_buf).await;
+
     // Create parent directories if they don't exist
     if let Some(parent) = path_buf.parent() {
         fs::create_dir_all(parent)
@@ -135,8 +547,9 @@ pub async fn write_file(path: String, content: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn create_directory(path: String) -> Result<(), String> {
+pub async fn create_directory(path: String, state: State<'_, AppState>) -> Result<(), String> {
     let path_buf = PathBuf::from(&path);
+    invalidate_directory_cache(&state, &path_buf).await;
 
     fs::create_dir_all(&path_buf)
         .map_err(|e| format!("Failed to create directory: {}", e))?;
@@ -145,8 +558,9 @@ pub async fn create_directory(path: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn delete_path(path: String) -> Result<(), String> {
+pub async fn delete_path(path: String, state: State<'_, AppState>) -> Result<(), String> {
     let path_buf = PathBuf::from(&path);
+    invalidate_directory_cache(&state, &path_buf).await;
 
     if !path_buf.exists() {
         return Ok(()); // Already doesn't exist
@@ -163,6 +577,28 @@ pub async fn delete_path(path: String) -> Result<(), String> {
     Ok(())
 }
 
+#[tauri::command]
+pub async fn move_path(from: String, to: String, state: State<'_, AppState>) -> Result<(), String> {
+    let from_buf = PathBuf::from(&from);
+    let to_buf = PathBuf::from(&to);
+
+    if !from_buf.exists() {
+        return Err(format!("Path does not exist: {}", from));
+    }
+
+    if to_buf.exists() {
+        return Err(format!("Destination already exists: {}", to));
+    }
+
+    invalidate_directory_cache(&state, &from_buf).await;
+    invalidate_directory_cache(&state, &to_buf).await;
+
+    fs::rename(&from_buf, &to_buf)
+        .map_err(|e| format!("Failed to move {}: {}", from, e))?;
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn path_exists(path: String) -> Result<bool, String> {
     Ok(PathBuf::from(&path).exists())
@@ -181,3 +617,174 @@ pub async fn get_app_data_dir() -> Result<String, String> {
         .map(|p| p.join("signalforge-dev").to_string_lossy().to_string())
         .ok_or_else(|| "Could not determine app data directory".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_stores;
+
+    fn hits(state: &AppState) -> u64 {
+        state.cache_hit_count.load(Ordering::Relaxed)
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn second_listing_is_served_from_cache() {
+        let _stores = isolated_stores();
+        let state = AppState::new();
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("index.php"), "<?php").unwrap();
+
+        let first = list_directory_cached(&state, dir.path().to_path_buf()).await.unwrap();
+        assert_eq!(hits(&state), 0);
+        let second = list_directory_cached(&state, dir.path().to_path_buf()).await.unwrap();
+
+        assert_eq!(hits(&state), 1);
+        let names = |entries: &[DirectoryEntry]| {
+            let mut names: Vec<String> = entries.iter().map(|e| e.name.clone()).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(&first), vec!["index.php", "src"]);
+        assert_eq!(names(&second), names(&first));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn invalidated_listing_is_read_again() {
+        let _stores = isolated_stores();
+        let state = AppState::new();
+        let dir = tempfile::tempdir().unwrap();
+        list_directory_cached(&state, dir.path().to_path_buf()).await.unwrap();
+
+        let new_file = dir.path().join("composer.json");
+        fs::write(&new_file, "{}").unwrap();
+        invalidate_directory_cache(&state, &new_file).await;
+        let entries = list_directory_cached(&state, dir.path().to_path_buf()).await.unwrap();
+
+        assert_eq!(hits(&state), 0);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "composer.json");
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[tokio::test]
+    async fn open_file_is_reported_in_use() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().to_string_lossy().to_string();
+
+        let usage = is_file_in_use(path).await.unwrap();
+
+        assert!(usage.in_use);
+        let pid = format!("({})", std::process::id());
+        assert!(usage.processes.iter().any(|p| p.ends_with(&pid)));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[tokio::test]
+    async fn closed_file_is_not_in_use() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("closed.txt");
+        fs::write(&path, "done").unwrap();
+
+        let usage = is_file_in_use(path.to_string_lossy().to_string()).await.unwrap();
+
+        assert!(!usage.in_use);
+        assert!(usage.processes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn missing_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.txt").to_string_lossy().to_string();
+
+        assert!(is_file_in_use(path).await.is_err());
+    }
+
+    #[test]
+    fn diff_contents_is_none_for_identical_text() {
+        assert!(diff_contents("compose.yml", "a\nb\n", "a\nb\n").is_none());
+    }
+
+    #[test]
+    fn diff_contents_reports_changed_lines() {
+        let old = "services:\n  php:\n    image: php:8.2-fpm\n";
+        let new = "services:\n  php:\n    image: php:8.3-fpm\n    restart: always\n";
+
+        let diff = diff_contents("compose.yml", old, new).unwrap();
+
+        assert_eq!((diff.additions, diff.deletions), (2, 1));
+        let kinds: Vec<(&str, &str)> = diff
+            .lines
+            .iter()
+            .map(|l| (l.kind.as_str(), l.content.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("unchanged", "services:"),
+                ("unchanged", "  php:"),
+                ("added", "    image: php:8.3-fpm"),
+                ("added", "    restart: always"),
+                ("removed", "    image: php:8.2-fpm"),
+            ]
+        );
+        let removed = diff.lines.iter().find(|l| l.kind == "removed").unwrap();
+        assert_eq!((removed.old_line, removed.new_line), (Some(3), None));
+    }
+
+    fn temp_file(content: &[u8]) -> (tempfile::TempDir, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.txt");
+        fs::write(&path, content).unwrap();
+        (dir, path.to_string_lossy().to_string())
+    }
+
+    #[tokio::test]
+    async fn read_file_range_boundaries() {
+        let (_dir, path) = temp_file(b"0123456789");
+
+        let start = read_file_range(path.clone(), 0, 4).await.unwrap();
+        assert_eq!((start.content.as_str(), start.offset, start.bytes_read), ("0123", 0, 4));
+        assert!(start.has_more);
+
+        let tail = read_file_range(path.clone(), 6, 4).await.unwrap();
+        assert_eq!(tail.content, "6789");
+        assert!(!tail.has_more);
+
+        let overlong = read_file_range(path.clone(), 8, 100).await.unwrap();
+        assert_eq!((overlong.content.as_str(), overlong.bytes_read), ("89", 2));
+        assert!(!overlong.has_more);
+
+        // Offsets past the end clamp to the end instead of failing
+        let past_end = read_file_range(path.clone(), 50, 4).await.unwrap();
+        assert_eq!((past_end.offset, past_end.bytes_read, past_end.total_size), (10, 0, 10));
+        assert!(!past_end.has_more);
+
+        let empty_read = read_file_range(path, 3, 0).await.unwrap();
+        assert_eq!(empty_read.bytes_read, 0);
+        assert!(empty_read.has_more);
+    }
+
+    #[tokio::test]
+    async fn read_file_range_rejects_directories_and_handles_empty_files() {
+        let (dir, _) = temp_file(b"");
+        assert!(read_file_range(dir.path().to_string_lossy().to_string(), 0, 10).await.is_err());
+
+        let (_dir, empty) = temp_file(b"");
+        let chunk = read_file_range(empty, 0, 10).await.unwrap();
+        assert_eq!((chunk.bytes_read, chunk.total_size), (0, 0));
+        assert!(!chunk.has_more);
+    }
+
+    #[tokio::test]
+    async fn read_file_lines_boundaries() {
+        let (_dir, path) = temp_file(b"one\r\ntwo\nthree\n");
+
+        assert_eq!(read_file_lines(path.clone(), 0, 2).await.unwrap(), vec!["one", "two"]);
+        assert_eq!(read_file_lines(path.clone(), 2, 10).await.unwrap(), vec!["three"]);
+        assert!(read_file_lines(path.clone(), 3, 10).await.unwrap().is_empty());
+        assert!(read_file_lines(path, 0, 0).await.unwrap().is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod commands;
 pub mod compose;
 pub mod config;
@@ -6,6 +7,13 @@ pub mod docker;
 pub mod filesystem;
 pub mod mkcert;
 pub mod nginx;
+pub mod php;
+pub mod secrets;
+pub mod telemetry;
+pub mod watcher;
+
+#[cfg(test)]
+mod test_support;
 
 use commands::AppState;
 
@@ -16,71 +24,205 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .manage(AppState::new())
         .invoke_handler(tauri::generate_handler![
+            // Startup
+            commands::initialize_app,
             // Docker commands
             commands::check_docker_connection,
             commands::connect_docker,
+            commands::connect_docker_remote,
             commands::list_containers,
+            commands::list_all_containers,
             commands::start_container,
             commands::stop_container,
             commands::restart_container,
+            commands::kill_container,
+            commands::update_container_resources,
+            commands::set_container_resources,
+            commands::get_container_resource_limits,
             commands::get_container_stats,
+            commands::subscribe_container_stats,
+            commands::unsubscribe_container_stats,
             commands::get_container_logs,
+            commands::tail_container_logs,
+            commands::untail_container_logs,
+            commands::copy_from_container,
+            commands::copy_to_container,
+            commands::upload_file_to_container,
+            commands::exec_container,
             commands::get_docker_info,
+            commands::get_docker_disk_usage,
+            commands::docker_system_prune,
+            commands::tag_image,
+            commands::list_image_tags,
+            commands::list_images,
+            commands::pull_image,
+            commands::remove_image,
+            commands::list_networks,
+            commands::create_network,
+            commands::remove_network,
+            commands::list_volumes,
+            commands::inspect_volume,
+            commands::create_volume,
+            commands::remove_volume,
+            commands::backup_volume,
             commands::get_network_topology,
+            commands::get_container_labels,
+            commands::get_container_network_settings,
+            commands::list_docker_contexts,
+            commands::use_docker_context,
+            commands::docker_login,
+            commands::docker_logout,
+            commands::list_authenticated_registries,
+            commands::register_project_for_monitoring,
+            commands::unregister_project_for_monitoring,
+            commands::get_cached_project_status,
+            // Audit log commands
+            audit::get_audit_log,
             // Filesystem commands
             filesystem::list_directory,
             filesystem::list_directory_recursive,
             filesystem::read_file,
+            filesystem::read_file_range,
+            filesystem::read_file_lines,
+            filesystem::get_file_metadata,
+            filesystem::is_file_in_use,
             filesystem::write_file,
             filesystem::create_directory,
             filesystem::delete_path,
+            filesystem::move_path,
+            filesystem::clear_directory_cache,
             filesystem::path_exists,
             filesystem::get_home_dir,
             filesystem::get_app_data_dir,
+            // File watcher commands
+            watcher::start_file_watch,
+            watcher::watch_project,
+            watcher::stop_file_watch,
+            watcher::list_file_watches,
             // Config commands
             config::get_app_config,
+            config::migrate_config_file,
             config::save_app_config,
             config::ensure_directories,
             config::reset_app_config,
+            config::set_auto_open_browser,
+            config::set_telemetry,
+            config::list_registry_credentials,
+            config::add_registry_credential,
+            config::remove_registry_credential,
             // Compose commands
             compose::list_projects,
             compose::get_project,
             compose::create_project,
+            compose::list_project_templates,
+            compose::import_project_from_compose,
             compose::update_project,
             compose::delete_project,
+            compose::get_project_secret,
+            compose::set_project_secret,
+            compose::assign_unique_network,
             compose::get_compose_content,
             compose::save_compose_content,
+            compose::get_compose_diff,
+            compose::add_extra_compose_file,
+            compose::remove_extra_compose_file,
+            compose::get_project_env,
+            compose::save_project_env,
+            compose::bootstrap_from_dotenv_example,
+            compose::apply_dotenv_to_project,
             compose::compose_up,
+            compose::compose_up_with_profiles,
+            compose::compose_up_dry_run,
             compose::compose_down,
+            compose::compose_down_and_clean,
             compose::compose_restart,
+            compose::compose_up_service,
+            compose::compose_down_service,
+            compose::compose_restart_service,
+            compose::compose_exec,
+            compose::compose_recreate,
+            compose::compose_scale,
+            compose::compose_build,
+            compose::pull_project_images,
             compose::compose_status,
+            compose::backup_named_volume,
+            compose::restore_named_volume,
+            compose::compose_logs_start,
+            compose::compose_logs_stop,
+            compose::get_service_logs_since,
+            compose::get_project_service_statuses,
+            compose::project_health_check,
+            compose::get_project_url,
+            compose::get_project_stats,
+            compose::backup_project,
+            compose::restore_project,
+            compose::export_project,
+            compose::import_project_from_archive,
             // Nginx commands
             nginx::list_vhosts,
             nginx::get_vhost,
             nginx::create_vhost,
+            nginx::clone_vhost,
+            nginx::generate_react_nginx_config,
             nginx::update_vhost,
             nginx::delete_vhost,
+            nginx::set_vhost_enabled,
+            nginx::update_ip_access_rules,
+            nginx::add_vhost_map,
+            nginx::remove_vhost_map,
+            nginx::set_vhost_rate_limit,
+            nginx::remove_vhost_rate_limit,
+            nginx::generate_security_headers_preset,
+            nginx::generate_default_error_pages,
+            nginx::list_nginx_includes,
+            nginx::create_nginx_include,
+            nginx::update_nginx_include,
+            nginx::delete_nginx_include,
+            nginx::generate_acme_challenge_dir,
+            nginx::evaluate_tls_config,
             nginx::get_vhost_config,
             nginx::save_vhost_config,
+            nginx::lint_nginx_config_content,
             nginx::test_nginx_config,
+            nginx::test_rewrite_rules,
             nginx::reload_nginx,
+            nginx::parse_nginx_access_log,
+            nginx::get_nginx_log_paths,
             nginx::generate_default_nginx_config,
+            // PHP-FPM commands
+            php::list_fpm_pools,
+            php::create_fpm_pool,
+            php::update_fpm_pool,
+            php::delete_fpm_pool,
             // mkcert commands
             mkcert::get_mkcert_status,
             mkcert::install_mkcert_ca,
+            mkcert::uninstall_mkcert_ca,
             mkcert::generate_certificate,
+            mkcert::generate_ip_certificate,
             mkcert::list_certificates,
+            mkcert::get_expiring_certificates,
             mkcert::get_certificate,
             mkcert::delete_certificate,
             mkcert::get_mkcert_install_instructions,
             // dnsmasq commands
             dnsmasq::get_dnsmasq_status,
             dnsmasq::configure_sig_tld,
+            dnsmasq::reload_dnsmasq,
             dnsmasq::list_sig_domains,
             dnsmasq::add_sig_domain,
             dnsmasq::remove_sig_domain,
+            dnsmasq::update_domain_ttl,
+            dnsmasq::add_domain_alias,
+            dnsmasq::remove_domain_alias,
+            dnsmasq::export_domains_as_dnsmasq_conf,
+            dnsmasq::apply_domains_to_dnsmasq,
             dnsmasq::test_domain_resolution,
+            dnsmasq::test_all_domains,
+            dnsmasq::get_domain_resolution_summary,
+            dnsmasq::trace_dns_resolution,
             dnsmasq::get_hosts_entries,
+            dnsmasq::find_dns_conflicts,
             dnsmasq::get_dnsmasq_install_instructions,
         ])
         .run(tauri::generate_context!())
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::process::Command;
 use chrono::Utc;
@@ -19,8 +20,27 @@ pub struct Certificate {
     pub key_path: String,
     pub created_at: i64,
     pub is_wildcard: bool,
+    #[serde(default = "default_cert_type")]
+    pub cert_type: String,
+    // notAfter of the certificate, as a unix timestamp
+    #[serde(default)]
+    pub expires_at: Option<i64>,
 }
 
+fn default_cert_type() -> String {
+    "domain".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UninstallResult {
+    pub success: bool,
+    pub message: String,
+    pub affected_certs: Vec<String>,
+}
+
+// Prefix of the nickname mkcert gives its root in NSS databases
+const MKCERT_NSS_NICKNAME: &str = "mkcert development CA";
+
 fn get_ssl_dir() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("/tmp"))
@@ -35,7 +55,7 @@ fn get_certs_file() -> PathBuf {
         .join("certificates.json")
 }
 
-fn load_certificates() -> Result<Vec<Certificate>, String> {
+pub(crate) fn load_certificates() -> Result<Vec<Certificate>, String> {
     let path = get_certs_file();
 
     if !path.exists() {
@@ -49,7 +69,7 @@ fn load_certificates() -> Result<Vec<Certificate>, String> {
         .map_err(|e| format!("Failed to parse certificates: {}", e))
 }
 
-fn save_certificates(certs: &[Certificate]) -> Result<(), String> {
+pub(crate) fn save_certificates(certs: &[Certificate]) -> Result<(), String> {
     let path = get_certs_file();
 
     if let Some(parent) = path.parent() {
@@ -66,6 +86,19 @@ fn save_certificates(certs: &[Certificate]) -> Result<(), String> {
     Ok(())
 }
 
+fn read_certificate_expiry(cert_path: &str) -> Result<i64, String> {
+    let pem_data = fs::read(cert_path)
+        .map_err(|e| format!("Failed to read certificate: {}", e))?;
+
+    let (_, pem) = x509_parser::pem::parse_x509_pem(&pem_data)
+        .map_err(|e| format!("Failed to decode certificate PEM: {}", e))?;
+    let cert = pem
+        .parse_x509()
+        .map_err(|e| format!("Failed to parse certificate: {}", e))?;
+
+    Ok(cert.validity().not_after.timestamp())
+}
+
 #[tauri::command]
 pub async fn get_mkcert_status() -> Result<MkcertStatus, String> {
     // Check if mkcert is installed
@@ -79,33 +112,13 @@ pub async fn get_mkcert_status() -> Result<MkcertStatus, String> {
         .filter(|o| o.status.success())
         .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
 
-    // Check if CA is installed
-    let ca_installed = if installed {
-        let caroot_output = Command::new("mkcert")
-            .arg("-CAROOT")
-            .output()
-            .ok();
-
-        caroot_output
-            .as_ref()
-            .map(|o| {
-                let path = String::from_utf8_lossy(&o.stdout).trim().to_string();
-                PathBuf::from(&path).join("rootCA.pem").exists()
-            })
-            .unwrap_or(false)
-    } else {
-        false
-    };
-
-    let ca_path = if ca_installed {
-        Command::new("mkcert")
-            .arg("-CAROOT")
-            .output()
-            .ok()
-            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-    } else {
-        None
-    };
+    // The CA files outlive `mkcert -uninstall`, so "installed" means the
+    // root is both present and trusted by the system
+    let caroot = if installed { get_caroot() } else { None };
+    let ca_path = caroot
+        .filter(|path| path.join("rootCA.pem").exists())
+        .map(|path| path.to_string_lossy().to_string());
+    let ca_installed = ca_path.is_some() && ca_in_trust_store();
 
     Ok(MkcertStatus {
         installed,
@@ -129,6 +142,164 @@ pub async fn install_mkcert_ca() -> Result<String, String> {
     }
 }
 
+fn get_caroot() -> Option<PathBuf> {
+    Command::new("mkcert")
+        .arg("-CAROOT")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| PathBuf::from(String::from_utf8_lossy(&o.stdout).trim()))
+}
+
+// mkcert copies its root into the distro's anchor directory as
+// mkcert_development_CA_<serial>.crt
+#[cfg(target_os = "linux")]
+fn ca_in_trust_store() -> bool {
+    const ANCHOR_DIRS: [&str; 4] = [
+        "/usr/local/share/ca-certificates",
+        "/etc/pki/ca-trust/source/anchors",
+        "/etc/ca-certificates/trust-source/anchors",
+        "/usr/share/pki/trust/anchors",
+    ];
+
+    ANCHOR_DIRS
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.file_name().to_string_lossy().starts_with("mkcert_development_CA_"))
+}
+
+// The root's common name is "mkcert <user>@<host>"
+#[cfg(target_os = "macos")]
+fn ca_in_trust_store() -> bool {
+    Command::new("security")
+        .args(["find-certificate", "-c", "mkcert ", "/Library/Keychains/System.keychain"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+// mkcert installs into the current user's root store; the listing shows the
+// root's organization, which is the same text as the NSS nickname prefix
+#[cfg(target_os = "windows")]
+fn ca_in_trust_store() -> bool {
+    Command::new("certutil")
+        .args(["-user", "-store", "Root"])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains(MKCERT_NSS_NICKNAME))
+        .unwrap_or(false)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn ca_in_trust_store() -> bool {
+    true
+}
+
+// Firefox profiles and the shared Chrome/Chromium NSS database
+fn find_nss_databases() -> Vec<PathBuf> {
+    let home = match dirs::home_dir() {
+        Some(home) => home,
+        None => return Vec::new(),
+    };
+
+    let mut databases = vec![home.join(".pki").join("nssdb")];
+
+    let profile_roots = [
+        home.join(".mozilla").join("firefox"),
+        home.join("snap").join("firefox").join("common").join(".mozilla").join("firefox"),
+        home.join("Library").join("Application Support").join("Firefox").join("Profiles"),
+    ];
+
+    for root in profile_roots {
+        if let Ok(entries) = fs::read_dir(&root) {
+            databases.extend(entries.flatten().map(|e| e.path()));
+        }
+    }
+
+    databases
+        .into_iter()
+        .filter(|dir| dir.join("cert9.db").exists())
+        .collect()
+}
+
+// Removes any mkcert roots left behind in NSS databases, returning how many
+// were deleted. Does nothing when certutil is not installed.
+fn remove_ca_from_nss() -> usize {
+    if Command::new("certutil").arg("-H").output().is_err() {
+        return 0;
+    }
+
+    let mut removed = 0;
+
+    for db in find_nss_databases() {
+        let db_arg = format!("sql:{}", db.to_string_lossy());
+
+        let listing = match Command::new("certutil").args(["-L", "-d", &db_arg]).output() {
+            Ok(output) if output.status.success() => output,
+            _ => continue,
+        };
+
+        let nicknames: Vec<String> = String::from_utf8_lossy(&listing.stdout)
+            .lines()
+            .filter(|line| line.starts_with(MKCERT_NSS_NICKNAME))
+            .filter_map(|line| line.rsplit_once("  ").map(|(name, _)| name.trim().to_string()))
+            .collect();
+
+        for nickname in nicknames {
+            let deleted = Command::new("certutil")
+                .args(["-D", "-d", &db_arg, "-n", &nickname])
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false);
+
+            if deleted {
+                removed += 1;
+            }
+        }
+    }
+
+    removed
+}
+
+#[tauri::command]
+pub async fn uninstall_mkcert_ca() -> Result<UninstallResult, String> {
+    let output = Command::new("mkcert")
+        .arg("-uninstall")
+        .output()
+        .map_err(|e| format!("Failed to uninstall mkcert CA: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let nss_removed = remove_ca_from_nss();
+
+    // The CA files stay in CAROOT: without the key, certificates issued so
+    // far could never be trusted again, even after `mkcert -install`
+    let affected_certs: Vec<String> = load_certificates()?
+        .into_iter()
+        .map(|c| c.domain)
+        .collect();
+
+    let mut message = "CA removed from the system trust stores.".to_string();
+    if nss_removed > 0 {
+        message.push_str(&format!(" Removed {} leftover NSS entries.", nss_removed));
+    }
+    if !affected_certs.is_empty() {
+        message.push_str(&format!(
+            " Warning: {} existing certificate(s) will no longer be trusted.",
+            affected_certs.len()
+        ));
+    }
+
+    Ok(UninstallResult {
+        success: true,
+        message,
+        affected_certs,
+    })
+}
+
 #[tauri::command]
 pub async fn generate_certificate(domain: String, wildcard: bool) -> Result<Certificate, String> {
     let ssl_dir = get_ssl_dir();
@@ -176,6 +347,8 @@ pub async fn generate_certificate(domain: String, wildcard: bool) -> Result<Cert
         key_path: key_path.to_string_lossy().to_string(),
         created_at: Utc::now().timestamp(),
         is_wildcard: wildcard,
+        cert_type: default_cert_type(),
+        expires_at: read_certificate_expiry(&cert_path.to_string_lossy()).ok(),
     };
 
     // Save to certificates list
@@ -189,9 +362,70 @@ pub async fn generate_certificate(domain: String, wildcard: bool) -> Result<Cert
     Ok(cert)
 }
 
+#[tauri::command]
+pub async fn generate_ip_certificate(ip_address: String) -> Result<Certificate, String> {
+    // Reject bad input before shelling out to mkcert
+    let ip: IpAddr = ip_address
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid IP address: {}", ip_address))?;
+    let ip_address = ip.to_string();
+
+    let ssl_dir = get_ssl_dir();
+    fs::create_dir_all(&ssl_dir)
+        .map_err(|e| format!("Failed to create SSL directory: {}", e))?;
+
+    let filename_base = format!("ip_{}", ip_address.replace(['.', ':'], "_"));
+    let cert_path = ssl_dir.join(format!("{}.crt", filename_base));
+    let key_path = ssl_dir.join(format!("{}.key", filename_base));
+
+    // mkcert adds IP SANs for positional arguments that parse as addresses
+    let output = Command::new("mkcert")
+        .arg("-cert-file")
+        .arg(&cert_path)
+        .arg("-key-file")
+        .arg(&key_path)
+        .arg(&ip_address)
+        .output()
+        .map_err(|e| format!("Failed to generate certificate: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let cert = Certificate {
+        domain: ip_address.clone(),
+        cert_path: cert_path.to_string_lossy().to_string(),
+        key_path: key_path.to_string_lossy().to_string(),
+        created_at: Utc::now().timestamp(),
+        is_wildcard: false,
+        cert_type: "ip".to_string(),
+        expires_at: read_certificate_expiry(&cert_path.to_string_lossy()).ok(),
+    };
+
+    let mut certs = load_certificates()?;
+    certs.retain(|c| c.domain != ip_address);
+    certs.push(cert.clone());
+    save_certificates(&certs)?;
+
+    Ok(cert)
+}
+
 #[tauri::command]
 pub async fn list_certificates() -> Result<Vec<Certificate>, String> {
-    let certs = load_certificates()?;
+    let mut certs = load_certificates()?;
+
+    // Certificates from older versions were saved without an expiry
+    let mut backfilled = false;
+    for cert in certs.iter_mut().filter(|c| c.expires_at.is_none()) {
+        if let Ok(expires_at) = read_certificate_expiry(&cert.cert_path) {
+            cert.expires_at = Some(expires_at);
+            backfilled = true;
+        }
+    }
+    if backfilled {
+        save_certificates(&certs)?;
+    }
 
     // Filter out certificates whose files no longer exist
     let valid_certs: Vec<Certificate> = certs
@@ -204,6 +438,23 @@ pub async fn list_certificates() -> Result<Vec<Certificate>, String> {
     Ok(valid_certs)
 }
 
+// Already expired certificates are included, soonest first
+#[tauri::command]
+pub async fn get_expiring_certificates(within_days: u64) -> Result<Vec<Certificate>, String> {
+    let cutoff = Utc::now()
+        .timestamp()
+        .saturating_add(i64::try_from(within_days.saturating_mul(86_400)).unwrap_or(i64::MAX));
+
+    let mut expiring: Vec<Certificate> = list_certificates()
+        .await?
+        .into_iter()
+        .filter(|c| c.expires_at.is_some_and(|expires_at| expires_at <= cutoff))
+        .collect();
+    expiring.sort_by_key(|c| c.expires_at);
+
+    Ok(expiring)
+}
+
 #[tauri::command]
 pub async fn get_certificate(domain: String) -> Result<Certificate, String> {
     let certs = load_certificates()?;
@@ -300,3 +551,24 @@ After installation, run `mkcert -install` to install the CA."#,
 
     Ok(instructions.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_stores;
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn invalid_ip_is_rejected_before_running_mkcert() {
+        let mut stores = isolated_stores();
+        let marker = stores.root.path().join("mkcert-ran");
+        stores.fake_command("mkcert", &format!("touch {}", marker.display()));
+
+        let result = generate_ip_certificate("not-an-ip".to_string()).await;
+
+        assert_eq!(result.unwrap_err(), "Invalid IP address: not-an-ip");
+        assert!(!marker.exists());
+        assert!(!get_ssl_dir().exists());
+        assert!(load_certificates().unwrap().is_empty());
+    }
+}
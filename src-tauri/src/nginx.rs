@@ -1,6 +1,12 @@
+use crate::php;
+use base64::Engine;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use uuid::Uuid;
 
@@ -14,6 +20,264 @@ pub struct NginxVhost {
     pub ssl_cert_path: Option<String>,
     pub ssl_key_path: Option<String>,
     pub config_path: String,
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+    #[serde(default)]
+    pub denylist: Vec<String>,
+    #[serde(default)]
+    pub maps: Vec<NginxMap>,
+    #[serde(default)]
+    pub acme_challenge_root: Option<String>,
+    #[serde(default)]
+    pub proxy_target: Option<String>,
+    #[serde(default)]
+    pub proxy_keepalive: Option<NginxKeepalive>,
+    #[serde(default)]
+    pub react_app: Option<ReactAppConfig>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default)]
+    pub websocket_enabled: bool,
+    #[serde(default = "default_websocket_path")]
+    pub websocket_path: String,
+    #[serde(default)]
+    pub security_headers: Option<SecurityHeadersConfig>,
+    #[serde(default)]
+    pub compression: Option<CompressionConfig>,
+    #[serde(default)]
+    pub basic_auth: Option<BasicAuthConfig>,
+    #[serde(default)]
+    pub ip_restrictions: Vec<IpRestriction>,
+    #[serde(default)]
+    pub upstreams: Vec<UpstreamConfig>,
+    #[serde(default)]
+    pub client_max_body_size: Option<String>,
+    #[serde(default)]
+    pub fastcgi: Option<FastcgiConfig>,
+    #[serde(default)]
+    pub redirects: Vec<RedirectRule>,
+    #[serde(default)]
+    pub cors: Option<CorsConfig>,
+    // FastCGI address PHP requests go to; the compose php service when unset
+    #[serde(default)]
+    pub fpm_upstream: Option<String>,
+    // Managed PHP-FPM pool to route PHP requests to; takes precedence over
+    // fpm_upstream
+    #[serde(default)]
+    pub fpm_pool: Option<String>,
+    #[serde(default)]
+    pub rewrites: Vec<RewriteRule>,
+    // HTTP status code -> page path
+    #[serde(default)]
+    pub error_pages: HashMap<u16, String>,
+    // Names of shared include snippets
+    #[serde(default)]
+    pub includes: Vec<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_websocket_path() -> String {
+    "/ws".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReactAppConfig {
+    pub static_port: u16,
+    pub api_port: u16,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NginxKeepalive {
+    pub connections: u32,
+    pub timeout_seconds: u32,
+    pub requests: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RateLimitConfig {
+    pub requests_per_second: u32,
+    pub burst: u32,
+    pub zone_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SecurityHeadersConfig {
+    pub hsts_max_age: Option<u64>,
+    pub csp: Option<String>,
+    pub x_frame_options: String,
+    pub x_content_type_options: bool,
+    pub referrer_policy: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CompressionConfig {
+    pub gzip_enabled: bool,
+    pub gzip_min_length: u32,
+    pub gzip_types: Vec<String>,
+    pub brotli_enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpstreamConfig {
+    pub name: String,
+    pub servers: Vec<UpstreamServer>,
+    pub balancing: LoadBalancingMethod,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpstreamServer {
+    pub address: String,
+    pub weight: Option<u32>,
+    pub max_fails: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancingMethod {
+    RoundRobin,
+    LeastConn,
+    IpHash,
+}
+
+// Values use nginx syntax, e.g. "32k" and "16 16k"
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FastcgiConfig {
+    pub fastcgi_buffer_size: Option<String>,
+    pub fastcgi_buffers: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RewriteRule {
+    pub pattern: String,
+    pub replacement: String,
+    pub flag: RewriteFlag,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RewriteFlag {
+    Last,
+    Break,
+    Redirect,
+    Permanent,
+}
+
+impl RewriteFlag {
+    fn as_str(self) -> &'static str {
+        match self {
+            RewriteFlag::Last => "last",
+            RewriteFlag::Break => "break",
+            RewriteFlag::Redirect => "redirect",
+            RewriteFlag::Permanent => "permanent",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RewriteTestResult {
+    // Path after applying the vhost's rules in order
+    pub resolved_path: String,
+    // Index of the rule that stopped processing, if any matched
+    pub matched_rule: Option<usize>,
+    pub status: u16,
+    pub location: Option<String>,
+}
+
+// An origin of "*" allows any origin
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    pub allow_credentials: bool,
+    pub max_age: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RedirectRule {
+    pub source_path: String,
+    pub dest_url: String,
+    pub permanent: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IpRestriction {
+    pub path: String,
+    pub allowed_ips: Vec<String>,
+    pub denied_ips: Vec<String>,
+}
+
+// Credentials are (username, password) pairs
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BasicAuthConfig {
+    pub realm: String,
+    pub credentials: Vec<(String, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum SecurityLevel {
+    Strict,
+    Moderate,
+    Permissive,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccessLogEntry {
+    pub remote_addr: String,
+    pub time_local: String,
+    pub method: String,
+    pub uri: String,
+    pub status: u16,
+    pub bytes_sent: u64,
+    pub referer: String,
+    pub user_agent: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NginxLogPaths {
+    pub access_logs: Vec<String>,
+    pub error_logs: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NginxInclude {
+    pub id: String,
+    pub name: String,
+    pub filename: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NginxMap {
+    pub variable: String,
+    pub default: String,
+    pub mappings: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TlsFinding {
+    pub severity: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TlsGradeReport {
+    pub grade: String,
+    pub findings: Vec<TlsFinding>,
+    pub recommendations: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NginxLintWarning {
+    pub line: usize,
+    pub severity: String,
+    pub message: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -21,14 +285,68 @@ pub struct NginxTestResult {
     pub success: bool,
     pub output: String,
     pub errors: Vec<String>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
-fn get_nginx_conf_dir() -> PathBuf {
+// Grades from best to worst; deductions move down this list
+const TLS_GRADES: [&str; 5] = ["A+", "A", "B", "C", "F"];
+
+// Directives the linter recognises; anything else is reported as unknown
+const KNOWN_DIRECTIVES: &[&str] = &[
+    "access_log", "add_header", "alias", "allow", "auth_basic", "auth_basic_user_file",
+    "autoindex", "brotli", "brotli_min_length", "brotli_static", "brotli_types", "charset",
+    "client_body_buffer_size", "client_max_body_size", "default_type", "deny", "error_log",
+    "error_page", "events", "expires", "fastcgi_buffer_size", "fastcgi_buffers", "fastcgi_index",
+    "fastcgi_intercept_errors", "fastcgi_param", "fastcgi_pass", "fastcgi_read_timeout",
+    "fastcgi_split_path_info", "gzip", "gzip_comp_level", "gzip_min_length", "gzip_proxied",
+    "gzip_types", "gzip_vary", "http", "http2", "if", "include", "index", "ip_hash", "keepalive",
+    "keepalive_requests", "keepalive_timeout", "least_conn", "limit_except", "limit_req", "listen",
+    "location", "log_format", "map", "proxy_buffering", "proxy_buffers", "proxy_buffer_size",
+    "proxy_connect_timeout", "proxy_http_version", "proxy_intercept_errors", "proxy_pass",
+    "proxy_read_timeout", "proxy_redirect", "proxy_send_timeout", "proxy_set_header", "resolver",
+    "return", "rewrite", "root", "sendfile", "server", "server_name", "server_tokens", "set",
+    "ssl_certificate", "ssl_certificate_key", "ssl_ciphers", "ssl_dhparam",
+    "ssl_prefer_server_ciphers", "ssl_protocols", "ssl_session_cache", "ssl_session_tickets",
+    "ssl_session_timeout", "ssl_stapling", "ssl_stapling_verify", "tcp_nodelay", "tcp_nopush",
+    "try_files", "types", "upstream",
+];
+
+// Blocks whose contents are key/value pairs rather than directives
+const NON_DIRECTIVE_BLOCKS: [&str; 3] = ["map", "types", "geo"];
+
+// Cipher name fragments the Mozilla guidelines no longer allow
+const WEAK_CIPHER_MARKERS: [&str; 7] = ["RC4", "DES", "MD5", "NULL", "EXPORT", "PSK", "SRP"];
+
+fn get_data_dir() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("/tmp"))
         .join("SignalforgeData")
-        .join("nginx")
-        .join("conf.d")
+}
+
+// signalforge-nginx mounts the data directory here, so any file a config
+// points at has to be named by its path on this side of the mount
+const CONTAINER_DATA_DIR: &str = "/var/lib/signalforge";
+
+// Paths outside the data directory are passed through unchanged
+fn container_path(host_path: &str) -> String {
+    match Path::new(host_path).strip_prefix(get_data_dir()) {
+        Ok(relative) => Path::new(CONTAINER_DATA_DIR).join(relative).to_string_lossy().to_string(),
+        Err(_) => host_path.to_string(),
+    }
+}
+
+pub(crate) fn get_nginx_conf_dir() -> PathBuf {
+    get_data_dir().join("nginx").join("conf.d")
+}
+
+// nginx only includes conf.d/*.conf, so this suffix takes a vhost out of service
+const DISABLED_SUFFIX: &str = ".disabled";
+
+const DEFAULT_FPM_UPSTREAM: &str = "php:9000";
+
+fn get_acme_root_dir() -> PathBuf {
+    get_data_dir().join("acme")
 }
 
 fn get_vhosts_file() -> PathBuf {
@@ -38,7 +356,7 @@ fn get_vhosts_file() -> PathBuf {
         .join("vhosts.json")
 }
 
-fn load_vhosts() -> Result<Vec<NginxVhost>, String> {
+pub(crate) fn load_vhosts() -> Result<Vec<NginxVhost>, String> {
     let path = get_vhosts_file();
 
     if !path.exists() {
@@ -52,7 +370,17 @@ fn load_vhosts() -> Result<Vec<NginxVhost>, String> {
         .map_err(|e| format!("Failed to parse vhosts: {}", e))
 }
 
-fn save_vhosts(vhosts: &[NginxVhost]) -> Result<(), String> {
+// Rewrites the config files of vhosts whose generated output depends on
+// state stored outside the vhost itself
+pub(crate) fn regenerate_vhost_configs(vhosts: &[&NginxVhost]) -> Result<(), String> {
+    for vhost in vhosts {
+        fs::write(&vhost.config_path, generate_vhost_config_content(vhost))
+            .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn save_vhosts(vhosts: &[NginxVhost]) -> Result<(), String> {
     let path = get_vhosts_file();
 
     if let Some(parent) = path.parent() {
@@ -69,161 +397,1916 @@ fn save_vhosts(vhosts: &[NginxVhost]) -> Result<(), String> {
     Ok(())
 }
 
-fn generate_vhost_config_content(vhost: &NginxVhost) -> String {
-    let mut config = String::new();
+// Markers delimiting the access rules so they can be replaced in place
+const ACCESS_RULES_BEGIN: &str = "# BEGIN signalforge access rules";
+const ACCESS_RULES_END: &str = "# END signalforge access rules";
 
-    // HTTP server block
-    config.push_str("server {\n");
-    config.push_str("    listen 80;\n");
-    config.push_str(&format!("    server_name {};\n", vhost.server_name));
+// Accepts a single IP address or a CIDR range
+fn validate_ip_rule(rule: &str) -> Result<(), String> {
+    let (addr, prefix) = match rule.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix)),
+        None => (rule, None),
+    };
 
-    if vhost.ssl_enabled {
-        config.push_str(&format!("    return 301 https://{}$request_uri;\n", vhost.server_name));
-        config.push_str("}\n\n");
+    let ip: IpAddr = addr
+        .parse()
+        .map_err(|_| format!("Invalid IP address: {}", rule))?;
 
-        // HTTPS server block
-        config.push_str("server {\n");
-        config.push_str("    listen 443 ssl http2;\n");
-        config.push_str(&format!("    server_name {};\n\n", vhost.server_name));
+    if let Some(prefix) = prefix {
+        let max = if ip.is_ipv4() { 32 } else { 128 };
+        match prefix.parse::<u8>() {
+            Ok(p) if p <= max => {}
+            _ => return Err(format!("Invalid CIDR prefix: {}", rule)),
+        }
+    }
 
-        if let (Some(cert), Some(key)) = (&vhost.ssl_cert_path, &vhost.ssl_key_path) {
-            config.push_str(&format!("    ssl_certificate {};\n", cert));
-            config.push_str(&format!("    ssl_certificate_key {};\n", key));
-            config.push_str("    ssl_protocols TLSv1.2 TLSv1.3;\n");
-            config.push_str("    ssl_ciphers ECDHE-ECDSA-AES128-GCM-SHA256:ECDHE-RSA-AES128-GCM-SHA256;\n");
-            config.push_str("    ssl_prefer_server_ciphers off;\n\n");
+    Ok(())
+}
+
+// Denied addresses are listed first since nginx applies the first matching rule
+fn generate_access_rules_block(allowlist: &[String], denylist: &[String]) -> String {
+    let mut block = format!("    {}\n", ACCESS_RULES_BEGIN);
+
+    for ip in denylist {
+        block.push_str(&format!("    deny {};\n", ip));
+    }
+
+    for ip in allowlist {
+        block.push_str(&format!("    allow {};\n", ip));
+    }
+
+    if !allowlist.is_empty() {
+        block.push_str("    deny all;\n");
+    }
+
+    block.push_str(&format!("    {}\n", ACCESS_RULES_END));
+    block
+}
+
+// Swaps the marked access rules section, inserting it before the first
+// server-level `root` directive when the config has none yet. Roots nested
+// in a location, like the ACME challenge one, are skipped.
+fn replace_access_rules(config: &str, block: &str) -> Result<String, String> {
+    let section = Regex::new(&format!(
+        r"(?s)[ \t]*{}\n.*?{}[^\n]*\n",
+        regex::escape(ACCESS_RULES_BEGIN),
+        regex::escape(ACCESS_RULES_END)
+    ))
+    .map_err(|e| format!("Failed to build access rules pattern: {}", e))?;
+
+    if section.is_match(config) {
+        return Ok(section.replace(config, regex::NoExpand(block)).to_string());
+    }
+
+    let root = Regex::new(r"(?m)^(    |\t)root\s")
+        .map_err(|e| format!("Failed to build root pattern: {}", e))?;
+
+    let position = root
+        .find(config)
+        .map(|m| m.start())
+        .ok_or_else(|| "Could not find a server-level root directive to attach access rules to".to_string())?;
+
+    Ok(format!("{}{}{}", &config[..position], block, &config[position..]))
+}
+
+// `map` blocks must live in the http context, so every vhost's maps are
+// collected into one shared file next to the vhost configs
+fn get_maps_config_path() -> PathBuf {
+    get_nginx_conf_dir().join("maps.conf")
+}
+
+fn validate_map_variable(variable: &str) -> Result<(), String> {
+    if variable.is_empty() || !variable.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid map variable name: {}", variable));
+    }
+    Ok(())
+}
+
+fn generate_maps_config_content(vhosts: &[NginxVhost]) -> String {
+    let mut config = String::from("# Signalforge Dev - shared map directives\n");
+
+    for vhost in vhosts {
+        for map in &vhost.maps {
+            let mut mappings: Vec<(&String, &String)> = map.mappings.iter().collect();
+            mappings.sort();
+
+            config.push_str(&format!("\n# {}\n", vhost.server_name));
+            config.push_str(&format!("map ${} $mapped_{} {{\n", map.variable, map.variable));
+            config.push_str(&format!("    default \"{}\";\n", map.default));
+            for (key, value) in mappings {
+                config.push_str(&format!("    \"{}\" \"{}\";\n", key, value));
+            }
+            config.push_str("}\n");
         }
     }
 
-    config.push_str(&format!("    root {};\n", vhost.document_root));
-    config.push_str("    index index.php index.html index.htm;\n\n");
+    config
+}
 
-    config.push_str("    location / {\n");
-    config.push_str("        try_files $uri $uri/ /index.php?$query_string;\n");
-    config.push_str("    }\n\n");
+fn write_shared_maps(vhosts: &[NginxVhost]) -> Result<(), String> {
+    let path = get_maps_config_path();
 
-    if vhost.php_enabled {
-        config.push_str("    location ~ \\.php$ {\n");
-        config.push_str("        fastcgi_pass php:9000;\n");
-        config.push_str("        fastcgi_index index.php;\n");
-        config.push_str("        fastcgi_param SCRIPT_FILENAME $document_root$fastcgi_script_name;\n");
-        config.push_str("        include fastcgi_params;\n");
-        config.push_str("    }\n\n");
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create nginx conf directory: {}", e))?;
     }
 
-    config.push_str("    location ~ /\\.ht {\n");
-    config.push_str("        deny all;\n");
-    config.push_str("    }\n\n");
+    fs::write(&path, generate_maps_config_content(vhosts))
+        .map_err(|e| format!("Failed to write nginx maps config: {}", e))
+}
 
-    config.push_str("    access_log /var/log/nginx/access.log;\n");
-    config.push_str("    error_log /var/log/nginx/error.log;\n");
-    config.push_str("}\n");
+// Snippets live outside conf.d so nginx only loads them where a vhost
+// includes them
+pub struct SnippetManager;
+
+impl SnippetManager {
+    fn store_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join("signalforge-dev")
+            .join("nginx_includes.json")
+    }
+
+    fn snippet_dir() -> PathBuf {
+        get_data_dir().join("nginx").join("snippets")
+    }
+
+    pub fn snippet_path(include: &NginxInclude) -> PathBuf {
+        Self::snippet_dir().join(&include.filename)
+    }
+
+    pub fn load() -> Result<Vec<NginxInclude>, String> {
+        let path = Self::store_path();
+
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read nginx includes: {}", e))?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse nginx includes: {}", e))
+    }
+
+    pub fn save(includes: &[NginxInclude]) -> Result<(), String> {
+        let path = Self::store_path();
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+
+        let content = serde_json::to_string_pretty(includes)
+            .map_err(|e| format!("Failed to serialize nginx includes: {}", e))?;
+
+        fs::write(&path, content)
+            .map_err(|e| format!("Failed to write nginx includes: {}", e))
+    }
+
+    fn write_snippet(include: &NginxInclude) -> Result<(), String> {
+        fs::create_dir_all(Self::snippet_dir())
+            .map_err(|e| format!("Failed to create nginx snippet directory: {}", e))?;
+
+        fs::write(Self::snippet_path(include), &include.content)
+            .map_err(|e| format!("Failed to write nginx snippet: {}", e))
+    }
+
+    fn remove_snippet(include: &NginxInclude) -> Result<(), String> {
+        let path = Self::snippet_path(include);
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to delete nginx snippet: {}", e))?;
+        }
+        Ok(())
+    }
+
+    fn validate(include: &NginxInclude, includes: &[NginxInclude]) -> Result<(), String> {
+        if include.name.is_empty() || include.name.contains(char::is_whitespace) {
+            return Err(format!("Invalid include name: {}", include.name));
+        }
+        if !include.filename.ends_with(".conf")
+            || !include
+                .filename
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        {
+            return Err(format!("Invalid include filename: {}", include.filename));
+        }
+        if let Some(other) = includes
+            .iter()
+            .find(|i| i.id != include.id && (i.name == include.name || i.filename == include.filename))
+        {
+            return Err(format!("Include '{}' already uses that name or filename", other.name));
+        }
+        Ok(())
+    }
+}
+
+// Unknown names are dropped here; update_vhost rejects them up front
+fn generate_includes_block(vhost: &NginxVhost) -> String {
+    if vhost.includes.is_empty() {
+        return String::new();
+    }
+
+    let includes = SnippetManager::load().unwrap_or_default();
+    let mut block = String::new();
+    for name in &vhost.includes {
+        if let Some(include) = includes.iter().find(|i| &i.name == name) {
+            block.push_str(&format!(
+                "    include {};\n",
+                container_path(&SnippetManager::snippet_path(include).to_string_lossy())
+            ));
+        }
+    }
+    if !block.is_empty() {
+        block.push('\n');
+    }
+    block
+}
+
+fn validate_includes(vhost: &NginxVhost) -> Result<(), String> {
+    if vhost.includes.is_empty() {
+        return Ok(());
+    }
+
+    let includes = SnippetManager::load()?;
+    for name in &vhost.includes {
+        if !includes.iter().any(|i| &i.name == name) {
+            return Err(format!("Include not found: {}", name));
+        }
+    }
+    Ok(())
+}
+
+// Like maps, `limit_req_zone` is only valid in the http context
+fn get_rate_limit_config_path() -> PathBuf {
+    get_nginx_conf_dir().join("signalforge-ratelimit.conf")
+}
+
+fn validate_rate_limit(vhost_id: &str, rate_limit: &RateLimitConfig, vhosts: &[NginxVhost]) -> Result<(), String> {
+    let zone = &rate_limit.zone_name;
+    if zone.is_empty() || !zone.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid rate limit zone name: {}", zone));
+    }
+    if rate_limit.requests_per_second == 0 {
+        return Err("Requests per second must be greater than zero".to_string());
+    }
+
+    // Each zone is declared once with a single rate
+    if let Some(other) = vhosts.iter().find(|v| {
+        v.id != vhost_id && v.rate_limit.as_ref().is_some_and(|r| &r.zone_name == zone)
+    }) {
+        return Err(format!("Rate limit zone '{}' is already used by {}", zone, other.server_name));
+    }
+
+    Ok(())
+}
+
+fn generate_rate_limit_config_content(vhosts: &[NginxVhost]) -> String {
+    let mut config = String::from("# Signalforge Dev - shared rate limit zones\n");
+
+    for vhost in vhosts {
+        if let Some(rate_limit) = &vhost.rate_limit {
+            config.push_str(&format!("\n# {}\n", vhost.server_name));
+            config.push_str(&format!(
+                "limit_req_zone $binary_remote_addr zone={}:10m rate={}r/s;\n",
+                rate_limit.zone_name, rate_limit.requests_per_second
+            ));
+        }
+    }
+
+    config
+}
+
+fn write_shared_rate_limits(vhosts: &[NginxVhost]) -> Result<(), String> {
+    let path = get_rate_limit_config_path();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create nginx conf directory: {}", e))?;
+    }
+
+    fs::write(&path, generate_rate_limit_config_content(vhosts))
+        .map_err(|e| format!("Failed to write nginx rate limit config: {}", e))
+}
+
+// `always` so the headers are also sent on error responses
+fn generate_security_headers_block(vhost: &NginxVhost, headers: &SecurityHeadersConfig) -> String {
+    let mut block = String::new();
+
+    // Browsers ignore HSTS received over plain HTTP
+    if let (true, Some(max_age)) = (vhost.ssl_enabled, headers.hsts_max_age) {
+        block.push_str(&format!(
+            "    add_header Strict-Transport-Security \"max-age={}; includeSubDomains\" always;\n",
+            max_age
+        ));
+    }
+    if let Some(csp) = headers.csp.as_deref().filter(|csp| !csp.is_empty()) {
+        block.push_str(&format!("    add_header Content-Security-Policy \"{}\" always;\n", csp));
+    }
+    if !headers.x_frame_options.is_empty() {
+        block.push_str(&format!("    add_header X-Frame-Options \"{}\" always;\n", headers.x_frame_options));
+    }
+    if headers.x_content_type_options {
+        block.push_str("    add_header X-Content-Type-Options \"nosniff\" always;\n");
+    }
+    if !headers.referrer_policy.is_empty() {
+        block.push_str(&format!("    add_header Referrer-Policy \"{}\" always;\n", headers.referrer_policy));
+    }
+
+    if !block.is_empty() {
+        block.push('\n');
+    }
+    block
+}
+
+fn generate_compression_block(compression: &CompressionConfig) -> String {
+    let mut block = String::new();
+    let types = compression.gzip_types.join(" ");
+
+    if compression.gzip_enabled {
+        block.push_str("    gzip on;\n");
+        block.push_str("    gzip_vary on;\n");
+        block.push_str("    gzip_proxied any;\n");
+        block.push_str(&format!("    gzip_min_length {};\n", compression.gzip_min_length));
+        if !types.is_empty() {
+            block.push_str(&format!("    gzip_types {};\n", types));
+        }
+    }
+
+    // Needs the ngx_brotli module; test_nginx_config warns when it is missing
+    if compression.brotli_enabled {
+        block.push_str("    brotli on;\n");
+        block.push_str("    brotli_static on;\n");
+        block.push_str(&format!("    brotli_min_length {};\n", compression.gzip_min_length));
+        if !types.is_empty() {
+            block.push_str(&format!("    brotli_types {};\n", types));
+        }
+    }
+
+    if !block.is_empty() {
+        block.push('\n');
+    }
+    block
+}
+
+fn get_htpasswd_path(vhost: &NginxVhost) -> PathBuf {
+    get_nginx_conf_dir().join(format!("{}.htpasswd", vhost.server_name.replace('.', "_")))
+}
+
+fn validate_basic_auth(basic_auth: &BasicAuthConfig) -> Result<(), String> {
+    if basic_auth.realm.contains(['"', '\n']) {
+        return Err(format!("Invalid basic auth realm: {}", basic_auth.realm));
+    }
+    if basic_auth.credentials.is_empty() {
+        return Err("Basic auth needs at least one user".to_string());
+    }
+    for (username, _) in &basic_auth.credentials {
+        if username.is_empty() || username.contains([':', '\n']) {
+            return Err(format!("Invalid basic auth username: {}", username));
+        }
+    }
+    Ok(())
+}
+
+// htpasswd entries in the `{SHA}` scheme, which nginx supports natively
+pub fn generate_htpasswd(creds: Vec<(String, String)>) -> String {
+    creds
+        .into_iter()
+        .map(|(username, password)| {
+            let digest = Sha1::digest(password.as_bytes());
+            format!(
+                "{}:{{SHA}}{}\n",
+                username,
+                base64::engine::general_purpose::STANDARD.encode(digest)
+            )
+        })
+        .collect()
+}
+
+// Keeps the vhost's htpasswd file in line with its basic auth settings
+fn write_htpasswd(vhost: &NginxVhost) -> Result<(), String> {
+    let path = get_htpasswd_path(vhost);
+
+    match &vhost.basic_auth {
+        Some(basic_auth) => fs::write(&path, generate_htpasswd(basic_auth.credentials.clone()))
+            .map_err(|e| format!("Failed to write htpasswd file: {}", e)),
+        None if path.exists() => {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove htpasswd file: {}", e))
+        }
+        None => Ok(()),
+    }
+}
+
+fn generate_basic_auth_block(vhost: &NginxVhost, basic_auth: &BasicAuthConfig) -> String {
+    format!(
+        "    auth_basic \"{}\";\n    auth_basic_user_file {};\n\n",
+        basic_auth.realm,
+        container_path(&get_htpasswd_path(vhost).to_string_lossy())
+    )
+}
+
+// WebSocket upgrades go to the same backend as regular proxied requests
+fn validate_websocket(vhost: &NginxVhost) -> Result<(), String> {
+    if !vhost.websocket_enabled {
+        return Ok(());
+    }
+    if vhost.proxy_target.is_none() {
+        return Err("WebSocket proxying requires a proxy target".to_string());
+    }
+    if !vhost.websocket_path.starts_with('/') || vhost.websocket_path.contains(char::is_whitespace) {
+        return Err(format!("Invalid WebSocket path: {}", vhost.websocket_path));
+    }
+    Ok(())
+}
+
+fn generate_rate_limit_directive(rate_limit: &RateLimitConfig) -> String {
+    format!(
+        "    limit_req zone={} burst={} nodelay;\n\n",
+        rate_limit.zone_name, rate_limit.burst
+    )
+}
+
+fn upstream_name(vhost: &NginxVhost) -> String {
+    format!("sig_{}", vhost.server_name.replace(['.', '-', '*'], "_"))
+}
+
+// Any add_header in a location stops the server-level ones from being
+// inherited, so locations that set their own headers repeat these
+fn inherited_add_headers(vhost: &NginxVhost) -> String {
+    let mut server_level = String::new();
+    if let Some(headers) = &vhost.security_headers {
+        server_level.push_str(&generate_security_headers_block(vhost, headers));
+    }
+    if let Some(cors) = &vhost.cors {
+        server_level.push_str(&generate_cors_block(cors));
+    }
+
+    server_level
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("add_header "))
+        .map(|line| format!("        {}\n", line))
+        .collect()
+}
+
+// Single-page app: static build served from document_root, unknown routes
+// fall back to index.html, and /api/ goes to the local backend
+fn generate_react_config_content(vhost: &NginxVhost, react: &ReactAppConfig) -> String {
+    let mut config = String::new();
+
+    config.push_str("server {\n");
+    config.push_str(&format!("    listen {};\n", react.static_port));
+    config.push_str(&format!("    server_name {};\n\n", vhost.server_name));
+
+    // Emitted even when empty so update_ip_access_rules has a server-level
+    // section to replace
+    config.push_str(&generate_access_rules_block(&vhost.allowlist, &vhost.denylist));
+    config.push('\n');
+
+    if let Some(rate_limit) = &vhost.rate_limit {
+        config.push_str(&generate_rate_limit_directive(rate_limit));
+    }
+
+    if let Some(headers) = &vhost.security_headers {
+        config.push_str(&generate_security_headers_block(vhost, headers));
+    }
+
+    if let Some(compression) = &vhost.compression {
+        config.push_str(&generate_compression_block(compression));
+    }
+
+    if let Some(basic_auth) = &vhost.basic_auth {
+        config.push_str(&generate_basic_auth_block(vhost, basic_auth));
+    }
+
+    // Uploads larger than nginx's 1M default fail before reaching PHP
+    if let Some(size) = &vhost.client_max_body_size {
+        config.push_str(&format!("    client_max_body_size {};\n\n", size));
+    }
+
+    if let Some(cors) = &vhost.cors {
+        config.push_str(&generate_cors_block(cors));
+    }
+
+    if !vhost.error_pages.is_empty() {
+        config.push_str(&generate_error_pages_block(&vhost.error_pages));
+    }
+
+    config.push_str(&generate_includes_block(vhost));
+
+    config.push_str(&format!("    root {};\n", vhost.document_root));
+    config.push_str("    index index.html;\n\n");
+
+    config.push_str("    location /api/ {\n");
+    config.push_str(&format!("        proxy_pass http://localhost:{};\n", react.api_port));
+    config.push_str("        proxy_http_version 1.1;\n");
+    config.push_str("        proxy_set_header Host $host;\n");
+    config.push_str("        proxy_set_header X-Real-IP $remote_addr;\n");
+    config.push_str("        proxy_set_header X-Forwarded-For $proxy_add_x_forwarded_for;\n");
+    config.push_str("        proxy_set_header X-Forwarded-Proto $scheme;\n");
+    config.push_str("    }\n\n");
+
+    let inherited_headers = inherited_add_headers(vhost);
+
+    // Hashed build assets never change under the same name
+    config.push_str("    location /static/ {\n");
+    config.push_str("        add_header Cache-Control \"max-age=31536000, immutable\";\n");
+    config.push_str(&inherited_headers);
+    config.push_str("        try_files $uri =404;\n");
+    config.push_str("    }\n\n");
+
+    config.push_str("    location = /index.html {\n");
+    config.push_str("        add_header Cache-Control \"no-cache\";\n");
+    config.push_str(&inherited_headers);
+    config.push_str("    }\n\n");
+
+    config.push_str("    location / {\n");
+    config.push_str("        try_files $uri $uri/ /index.html;\n");
+    config.push_str("    }\n\n");
+
+    config.push_str("    access_log /var/log/nginx/access.log;\n");
+    config.push_str("    error_log /var/log/nginx/error.log;\n");
+    config.push_str("}\n");
+
+    config
+}
+
+fn targets_upstream(vhost: &NginxVhost, upstream: &UpstreamConfig) -> bool {
+    vhost.proxy_target.as_deref() == Some(upstream.name.as_str())
+}
+
+fn generate_keepalive_directives(keepalive: &NginxKeepalive) -> String {
+    format!(
+        "    keepalive {};\n    keepalive_timeout {}s;\n    keepalive_requests {};\n",
+        keepalive.connections, keepalive.timeout_seconds, keepalive.requests
+    )
+}
+
+// What proxy_pass points at: a custom upstream named by proxy_target, the
+// generated keepalive pool, or the target address itself
+fn proxy_backend(vhost: &NginxVhost) -> Option<String> {
+    let target = vhost.proxy_target.as_ref()?;
+
+    if vhost.proxy_keepalive.is_some() && !vhost.upstreams.iter().any(|u| targets_upstream(vhost, u)) {
+        Some(upstream_name(vhost))
+    } else {
+        Some(target.clone())
+    }
+}
+
+// Upstream names share the http context with every other vhost
+fn validate_upstreams(vhost: &NginxVhost, vhosts: &[NginxVhost]) -> Result<(), String> {
+    for upstream in &vhost.upstreams {
+        if upstream.name.is_empty()
+            || !upstream.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            || upstream.name.starts_with("sig_")
+        {
+            return Err(format!("Invalid upstream name: {}", upstream.name));
+        }
+        if upstream.servers.is_empty() {
+            return Err(format!("Upstream {} needs at least one server", upstream.name));
+        }
+        if upstream
+            .servers
+            .iter()
+            .any(|s| s.address.is_empty() || s.address.contains(|c: char| c.is_whitespace() || c == ';'))
+        {
+            return Err(format!("Invalid server address in upstream {}", upstream.name));
+        }
+        if vhost.upstreams.iter().filter(|u| u.name == upstream.name).count() > 1 {
+            return Err(format!("Upstream {} is defined twice", upstream.name));
+        }
+        if let Some(other) = vhosts
+            .iter()
+            .find(|v| v.id != vhost.id && v.upstreams.iter().any(|u| u.name == upstream.name))
+        {
+            return Err(format!("Upstream {} is already defined by {}", upstream.name, other.server_name));
+        }
+    }
+    Ok(())
+}
+
+const DEFAULT_ERROR_PAGE_CODES: [u16; 6] = [403, 404, 500, 502, 503, 504];
+
+fn validate_error_pages(error_pages: &HashMap<u16, String>) -> Result<(), String> {
+    for (code, path) in error_pages {
+        if !(300..=599).contains(code) {
+            return Err(format!("Invalid error page status code: {}", code));
+        }
+        if !path.starts_with('/') || path.contains(|c: char| c.is_whitespace() || c == ';' || c == '{') {
+            return Err(format!("Invalid error page path: {}", path));
+        }
+    }
+    Ok(())
+}
+
+// `server_tokens off` also drops the version from nginx's own error pages
+fn generate_error_pages_block(error_pages: &HashMap<u16, String>) -> String {
+    let mut pages: Vec<(&u16, &String)> = error_pages.iter().collect();
+    pages.sort();
+
+    let mut block = String::from("    server_tokens off;\n");
+    for (code, path) in pages {
+        block.push_str(&format!("    error_page {} {};\n", code, path));
+    }
+    block.push('\n');
+    block
+}
+
+// Patterns and replacements are quoted so regex braces and spaces survive
+// nginx's tokenizer
+fn validate_rewrite(rule: &RewriteRule) -> Result<(), String> {
+    let unquotable = |value: &str| value.is_empty() || value.contains(['"', '\n', '\r']);
+
+    if unquotable(&rule.pattern) {
+        return Err(format!("Invalid rewrite pattern: {}", rule.pattern));
+    }
+    if unquotable(&rule.replacement) {
+        return Err(format!("Invalid rewrite replacement: {}", rule.replacement));
+    }
+    Ok(())
+}
+
+// How a location serves requests: proxied, or from document_root
+fn generate_content_directives(vhost: &NginxVhost) -> String {
+    let mut block = String::new();
+
+    for rule in &vhost.rewrites {
+        block.push_str(&format!(
+            "        rewrite \"{}\" \"{}\" {};\n",
+            rule.pattern,
+            rule.replacement,
+            rule.flag.as_str()
+        ));
+    }
+
+    match proxy_backend(vhost) {
+        Some(backend) => {
+            block.push_str(&format!("        proxy_pass http://{};\n", backend));
+            if vhost.proxy_keepalive.is_some() {
+                block.push_str("        proxy_http_version 1.1;\n");
+                block.push_str("        proxy_set_header Connection \"\";\n");
+            }
+            block.push_str("        proxy_set_header Host $host;\n");
+            block.push_str("        proxy_set_header X-Real-IP $remote_addr;\n");
+            block.push_str("        proxy_set_header X-Forwarded-For $proxy_add_x_forwarded_for;\n");
+            block.push_str("        proxy_set_header X-Forwarded-Proto $scheme;\n");
+            // Let error_page replace the backend's own error responses
+            if !vhost.error_pages.is_empty() {
+                block.push_str("        proxy_intercept_errors on;\n");
+            }
+        }
+        None => {
+            block.push_str("        try_files $uri $uri/ /index.php?$query_string;\n");
+        }
+    }
+
+    block
+}
+
+// A pool that has since become unreachable falls back to fpm_upstream;
+// update_vhost and the pool commands keep that from happening silently
+fn fastcgi_address(vhost: &NginxVhost) -> String {
+    let pool_address = vhost.fpm_pool.as_ref().and_then(|name| {
+        php::load_pools()
+            .unwrap_or_default()
+            .iter()
+            .find(|p| &p.name == name)
+            .and_then(|p| php::pool_fastcgi_address(p).ok())
+    });
+
+    pool_address
+        .or_else(|| vhost.fpm_upstream.clone())
+        .unwrap_or_else(|| DEFAULT_FPM_UPSTREAM.to_string())
+}
+
+fn generate_php_location(vhost: &NginxVhost, indent: &str) -> String {
+    let mut block = String::new();
+    block.push_str(&format!("{}location ~ \\.php$ {{\n", indent));
+    block.push_str(&format!("{}    fastcgi_pass {};\n", indent, fastcgi_address(vhost)));
+    block.push_str(&format!("{}    fastcgi_index index.php;\n", indent));
+    block.push_str(&format!("{}    fastcgi_param SCRIPT_FILENAME $document_root$fastcgi_script_name;\n", indent));
+    block.push_str(&format!("{}    include fastcgi_params;\n", indent));
+    if !vhost.error_pages.is_empty() {
+        block.push_str(&format!("{}    fastcgi_intercept_errors on;\n", indent));
+    }
+    if let Some(fastcgi) = &vhost.fastcgi {
+        if let Some(size) = &fastcgi.fastcgi_buffer_size {
+            block.push_str(&format!("{}    fastcgi_buffer_size {};\n", indent, size));
+        }
+        if let Some(buffers) = &fastcgi.fastcgi_buffers {
+            block.push_str(&format!("{}    fastcgi_buffers {};\n", indent, buffers));
+        }
+    }
+    block.push_str(&format!("{}}}\n", indent));
+    block
+}
+
+fn validate_size(value: &str, what: &str) -> Result<(), String> {
+    let digits = value.trim_end_matches(['k', 'K', 'm', 'M', 'g', 'G']);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) || value.len() - digits.len() > 1 {
+        return Err(format!("Invalid {}: {}", what, value));
+    }
+    Ok(())
+}
+
+fn validate_body_and_buffers(vhost: &NginxVhost) -> Result<(), String> {
+    if let Some(size) = &vhost.client_max_body_size {
+        validate_size(size, "client_max_body_size")?;
+    }
+    if let Some(fastcgi) = &vhost.fastcgi {
+        if let Some(size) = &fastcgi.fastcgi_buffer_size {
+            validate_size(size, "fastcgi_buffer_size")?;
+        }
+        // "<number> <size>"
+        if let Some(buffers) = &fastcgi.fastcgi_buffers {
+            match buffers.split_once(' ') {
+                Some((count, size)) if !count.is_empty() && count.chars().all(|c| c.is_ascii_digit()) => {
+                    validate_size(size, "fastcgi_buffers")?
+                }
+                _ => return Err(format!("Invalid fastcgi_buffers: {}", buffers)),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_cors(cors: &CorsConfig) -> Result<(), String> {
+    if cors.allowed_origins.is_empty() {
+        return Err("CORS needs at least one allowed origin".to_string());
+    }
+    for origin in cors.allowed_origins.iter().filter(|o| *o != "*") {
+        let valid = reqwest::Url::parse(origin).is_ok_and(|url| {
+            matches!(url.scheme(), "http" | "https") && url.path() == "/" && !origin.ends_with('/')
+        });
+        if !valid || origin.contains('"') {
+            return Err(format!("Invalid CORS origin: {}", origin));
+        }
+    }
+
+    let token = |value: &String| !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if let Some(method) = cors.allowed_methods.iter().find(|m| !token(m)) {
+        return Err(format!("Invalid CORS method: {}", method));
+    }
+    if let Some(header) = cors.allowed_headers.iter().find(|h| !token(h)) {
+        return Err(format!("Invalid CORS header: {}", header));
+    }
+
+    Ok(())
+}
+
+// Access-Control-Allow-Origin takes a single value, so the request's Origin
+// is echoed back when it is on the list. Browsers also refuse "*" together
+// with credentials, so that case echoes the origin as well.
+fn generate_cors_block(cors: &CorsConfig) -> String {
+    let wildcard = cors.allowed_origins.iter().any(|o| o == "*");
+    let mut block = String::from("    # CORS\n");
+
+    if wildcard && !cors.allow_credentials {
+        block.push_str("    add_header Access-Control-Allow-Origin \"*\" always;\n");
+    } else {
+        if wildcard {
+            block.push_str("    set $cors_origin $http_origin;\n");
+        } else {
+            let origins: Vec<String> = cors.allowed_origins.iter().map(|o| regex::escape(o)).collect();
+            block.push_str("    set $cors_origin \"\";\n");
+            block.push_str(&format!(
+                "    if ($http_origin ~* \"^({})$\") {{\n        set $cors_origin $http_origin;\n    }}\n",
+                origins.join("|")
+            ));
+        }
+        // nginx skips add_header when the value is empty
+        block.push_str("    add_header Access-Control-Allow-Origin $cors_origin always;\n");
+        block.push_str("    add_header Vary Origin always;\n");
+    }
+
+    if !cors.allowed_methods.is_empty() {
+        block.push_str(&format!(
+            "    add_header Access-Control-Allow-Methods \"{}\" always;\n",
+            cors.allowed_methods.join(", ")
+        ));
+    }
+    if !cors.allowed_headers.is_empty() {
+        block.push_str(&format!(
+            "    add_header Access-Control-Allow-Headers \"{}\" always;\n",
+            cors.allowed_headers.join(", ")
+        ));
+    }
+    if cors.allow_credentials {
+        block.push_str("    add_header Access-Control-Allow-Credentials \"true\" always;\n");
+    }
+    block.push_str(&format!("    add_header Access-Control-Max-Age {} always;\n", cors.max_age));
+
+    // Answer preflight requests here instead of passing them to the app
+    block.push_str("    if ($request_method = OPTIONS) {\n");
+    block.push_str("        return 204;\n");
+    block.push_str("    }\n\n");
+
+    block
+}
+
+fn validate_redirect(redirect: &RedirectRule) -> Result<(), String> {
+    let unsafe_char = |c: char| c.is_whitespace() || matches!(c, '{' | '}' | ';' | '"');
+
+    if !redirect.source_path.starts_with('/') || redirect.source_path.contains(unsafe_char) {
+        return Err(format!("Invalid redirect source: {}", redirect.source_path));
+    }
+
+    let dest = &redirect.dest_url;
+    let valid_dest = if dest.starts_with('/') {
+        !dest.starts_with("//")
+    } else {
+        reqwest::Url::parse(dest).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+    };
+    if !valid_dest || dest.contains(unsafe_char) {
+        return Err(format!("Redirect target must be an absolute URL or path: {}", dest));
+    }
+
+    Ok(())
+}
+
+// Exact-match locations, so /old doesn't also redirect /older
+fn generate_redirect_locations(redirects: &[RedirectRule]) -> String {
+    redirects
+        .iter()
+        .map(|redirect| {
+            let code = if redirect.permanent { 301 } else { 302 };
+            format!(
+                "    location = {} {{\n        return {} {};\n    }}\n\n",
+                redirect.source_path, code, redirect.dest_url
+            )
+        })
+        .collect()
+}
+
+fn validate_ip_restriction(restriction: &IpRestriction) -> Result<(), String> {
+    if !restriction.path.starts_with('/') || restriction.path.contains(|c: char| c.is_whitespace() || c == '{') {
+        return Err(format!("Invalid restricted path: {}", restriction.path));
+    }
+    for rule in restriction.allowed_ips.iter().chain(restriction.denied_ips.iter()) {
+        validate_ip_rule(rule)?;
+    }
+    Ok(())
+}
+
+// `^~` stops the regex PHP location from taking over requests under the
+// path, so PHP gets its own nested location that inherits the rules
+fn generate_ip_restriction_location(vhost: &NginxVhost, restriction: &IpRestriction) -> String {
+    let mut block = format!("    location ^~ {} {{\n", restriction.path);
+
+    for ip in &restriction.denied_ips {
+        block.push_str(&format!("        deny {};\n", ip));
+    }
+    for ip in &restriction.allowed_ips {
+        block.push_str(&format!("        allow {};\n", ip));
+    }
+    if !restriction.allowed_ips.is_empty() {
+        block.push_str("        deny all;\n");
+    }
+    block.push('\n');
+
+    block.push_str(&generate_content_directives(vhost));
+    if vhost.php_enabled && vhost.proxy_target.is_none() {
+        block.push('\n');
+        block.push_str(&generate_php_location(vhost, "        "));
+    }
+
+    block.push_str("    }\n\n");
+    block
+}
+
+pub(crate) fn generate_vhost_config_content(vhost: &NginxVhost) -> String {
+    if let Some(react) = &vhost.react_app {
+        return generate_react_config_content(vhost, react);
+    }
+
+    let mut config = String::new();
+
+    // Upstreams must be declared outside the server block; a pooled
+    // upstream keeps connections open instead of reconnecting per request
+    for upstream in &vhost.upstreams {
+        config.push_str(&format!("upstream {} {{\n", upstream.name));
+        match upstream.balancing {
+            LoadBalancingMethod::RoundRobin => {}
+            LoadBalancingMethod::LeastConn => config.push_str("    least_conn;\n"),
+            LoadBalancingMethod::IpHash => config.push_str("    ip_hash;\n"),
+        }
+        for server in &upstream.servers {
+            let mut line = format!("    server {}", server.address);
+            if let Some(weight) = server.weight {
+                line.push_str(&format!(" weight={}", weight));
+            }
+            if let Some(max_fails) = server.max_fails {
+                line.push_str(&format!(" max_fails={}", max_fails));
+            }
+            config.push_str(&format!("{};\n", line));
+        }
+        if let (true, Some(keepalive)) = (targets_upstream(vhost, upstream), &vhost.proxy_keepalive) {
+            config.push_str(&generate_keepalive_directives(keepalive));
+        }
+        config.push_str("}\n\n");
+    }
+
+    if let (Some(target), Some(keepalive)) = (&vhost.proxy_target, &vhost.proxy_keepalive) {
+        if !vhost.upstreams.iter().any(|u| targets_upstream(vhost, u)) {
+            config.push_str(&format!("upstream {} {{\n", upstream_name(vhost)));
+            config.push_str(&format!("    server {};\n", target));
+            config.push_str(&generate_keepalive_directives(keepalive));
+            config.push_str("}\n\n");
+        }
+    }
+
+    // HTTP server block
+    config.push_str("server {\n");
+    config.push_str("    listen 80;\n");
+    config.push_str(&format!("    server_name {};\n", vhost.server_name));
+
+    // Challenges arrive over plain HTTP, so this has to be served from the
+    // port 80 block ahead of the HTTPS redirect
+    if let Some(acme_root) = &vhost.acme_challenge_root {
+        config.push_str("\n    location ^~ /.well-known/acme-challenge/ {\n");
+        config.push_str(&format!("        root {};\n", container_path(acme_root)));
+        config.push_str("        allow all;\n");
+        // The CA can't log in, and without SSL auth_basic sits in this block
+        if vhost.basic_auth.is_some() {
+            config.push_str("        auth_basic off;\n");
+        }
+        config.push_str("    }\n\n");
+    }
+
+    if vhost.ssl_enabled {
+        if vhost.acme_challenge_root.is_some() {
+            // A server-level return would run before location matching
+            config.push_str("    location / {\n");
+            config.push_str(&format!("        return 301 https://{}$request_uri;\n", vhost.server_name));
+            config.push_str("    }\n");
+        } else {
+            config.push_str(&format!("    return 301 https://{}$request_uri;\n", vhost.server_name));
+        }
+        config.push_str("}\n\n");
+
+        // HTTPS server block
+        config.push_str("server {\n");
+        config.push_str("    listen 443 ssl http2;\n");
+        config.push_str(&format!("    server_name {};\n\n", vhost.server_name));
+
+        if let (Some(cert), Some(key)) = (&vhost.ssl_cert_path, &vhost.ssl_key_path) {
+            config.push_str(&format!("    ssl_certificate {};\n", container_path(cert)));
+            config.push_str(&format!("    ssl_certificate_key {};\n", container_path(key)));
+            config.push_str("    ssl_protocols TLSv1.2 TLSv1.3;\n");
+            config.push_str("    ssl_ciphers ECDHE-ECDSA-AES128-GCM-SHA256:ECDHE-RSA-AES128-GCM-SHA256;\n");
+            config.push_str("    ssl_prefer_server_ciphers off;\n\n");
+        }
+    }
+
+    // Emitted even when empty so update_ip_access_rules has a server-level
+    // section to replace
+    config.push_str(&generate_access_rules_block(&vhost.allowlist, &vhost.denylist));
+    config.push('\n');
+
+    if let Some(rate_limit) = &vhost.rate_limit {
+        config.push_str(&generate_rate_limit_directive(rate_limit));
+    }
+
+    if let Some(headers) = &vhost.security_headers {
+        config.push_str(&generate_security_headers_block(vhost, headers));
+    }
+
+    if let Some(compression) = &vhost.compression {
+        config.push_str(&generate_compression_block(compression));
+    }
+
+    if let Some(basic_auth) = &vhost.basic_auth {
+        config.push_str(&generate_basic_auth_block(vhost, basic_auth));
+    }
+
+    // Uploads larger than nginx's 1M default fail before reaching PHP
+    if let Some(size) = &vhost.client_max_body_size {
+        config.push_str(&format!("    client_max_body_size {};\n\n", size));
+    }
+
+    if let Some(cors) = &vhost.cors {
+        config.push_str(&generate_cors_block(cors));
+    }
+
+    if !vhost.error_pages.is_empty() {
+        config.push_str(&generate_error_pages_block(&vhost.error_pages));
+    }
+
+    config.push_str(&generate_includes_block(vhost));
+
+    config.push_str(&format!("    root {};\n", vhost.document_root));
+    config.push_str("    index index.php index.html index.htm;\n\n");
+
+    if let (true, Some(backend)) = (vhost.websocket_enabled, proxy_backend(vhost)) {
+        config.push_str(&format!("    location {} {{\n", vhost.websocket_path));
+        config.push_str(&format!("        proxy_pass http://{};\n", backend));
+        config.push_str("        proxy_http_version 1.1;\n");
+        config.push_str("        proxy_set_header Upgrade $http_upgrade;\n");
+        config.push_str("        proxy_set_header Connection \"upgrade\";\n");
+        config.push_str("        proxy_set_header Host $host;\n");
+        // Idle sockets would otherwise be dropped after the default 60s
+        config.push_str("        proxy_read_timeout 3600s;\n");
+        config.push_str("    }\n\n");
+    }
+
+    config.push_str(&generate_redirect_locations(&vhost.redirects));
+
+    for restriction in &vhost.ip_restrictions {
+        config.push_str(&generate_ip_restriction_location(vhost, restriction));
+    }
+
+    config.push_str("    location / {\n");
+    config.push_str(&generate_content_directives(vhost));
+    config.push_str("    }\n\n");
+
+    if vhost.php_enabled {
+        config.push_str(&generate_php_location(vhost, "    "));
+        config.push('\n');
+    }
+
+    config.push_str("    location ~ /\\.ht {\n");
+    config.push_str("        deny all;\n");
+    config.push_str("    }\n\n");
+
+    config.push_str("    access_log /var/log/nginx/access.log;\n");
+    config.push_str("    error_log /var/log/nginx/error.log;\n");
+    config.push_str("}\n");
+
+    config
+}
+
+// Values of every occurrence of a directive, e.g. `ssl_protocols`
+fn directive_values(config: &str, directive: &str) -> Vec<String> {
+    Regex::new(&format!(r"(?m)^\s*{}\s+([^;]*);", regex::escape(directive)))
+        .map(|re| {
+            re.captures_iter(config)
+                .map(|c| c[1].trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Grades a config against the Mozilla SSL Configuration Generator's
+// intermediate/modern profiles
+fn grade_tls_config(config: &str) -> TlsGradeReport {
+    let mut findings = Vec::new();
+    let mut recommendations = Vec::new();
+    let mut deductions = 0;
+    let mut worst_allowed = 0;
+
+    let mut finding = |severity: &str, message: &str| {
+        findings.push(TlsFinding {
+            severity: severity.to_string(),
+            message: message.to_string(),
+        });
+    };
+
+    let protocols: Vec<String> = directive_values(config, "ssl_protocols")
+        .iter()
+        .flat_map(|v| v.split_whitespace().map(str::to_string).collect::<Vec<_>>())
+        .collect();
+
+    if protocols.is_empty() {
+        finding("warning", "ssl_protocols is not set; older nginx releases default to TLSv1 and TLSv1.1");
+        recommendations.push("Set `ssl_protocols TLSv1.2 TLSv1.3;`".to_string());
+    }
+
+    if protocols.iter().any(|p| p.starts_with("SSL")) {
+        finding("critical", "SSLv3 or older protocols are enabled");
+        recommendations.push("Remove SSLv2/SSLv3 from ssl_protocols".to_string());
+        worst_allowed = 4;
+    } else if protocols.iter().any(|p| p == "TLSv1" || p == "TLSv1.1") {
+        finding("critical", "TLSv1.0 or TLSv1.1 is enabled");
+        recommendations.push("Restrict ssl_protocols to TLSv1.2 and TLSv1.3".to_string());
+        worst_allowed = 3;
+    }
+
+    let ciphers = directive_values(config, "ssl_ciphers").join(":");
+    let weak: Vec<&str> = ciphers
+        .split(':')
+        .filter(|c| WEAK_CIPHER_MARKERS.iter().any(|m| c.to_uppercase().contains(m)))
+        .collect();
+    if !weak.is_empty() {
+        finding("critical", &format!("Weak cipher suites enabled: {}", weak.join(", ")));
+        recommendations.push("Use the Mozilla intermediate cipher list (ECDHE with AES-GCM or ChaCha20)".to_string());
+        deductions += 2;
+    }
+
+    let has_hsts = directive_values(config, "add_header")
+        .iter()
+        .any(|h| h.to_lowercase().starts_with("strict-transport-security"));
+    if !has_hsts {
+        finding("warning", "HSTS header is not sent");
+        recommendations.push("Add `add_header Strict-Transport-Security \"max-age=63072000\" always;`".to_string());
+        deductions += 1;
+    }
+
+    if directive_values(config, "ssl_prefer_server_ciphers").is_empty() {
+        finding("warning", "ssl_prefer_server_ciphers is not set");
+        recommendations.push("Set `ssl_prefer_server_ciphers off;` as Mozilla recommends for TLSv1.2+".to_string());
+    }
+
+    let index = deductions.max(worst_allowed).min(TLS_GRADES.len() - 1);
+
+    TlsGradeReport {
+        grade: TLS_GRADES[index].to_string(),
+        findings,
+        recommendations,
+    }
+}
+
+struct LintBlock {
+    name: String,
+    line: usize,
+    fastcgi_pass_line: Option<usize>,
+    has_fastcgi_include: bool,
+    ssl_certificate_line: Option<usize>,
+    has_ssl_certificate_key: bool,
+}
+
+fn lint_warning(line: usize, severity: &str, message: String) -> NginxLintWarning {
+    NginxLintWarning {
+        line,
+        severity: severity.to_string(),
+        message,
+    }
+}
+
+fn close_lint_block(block: LintBlock, warnings: &mut Vec<NginxLintWarning>) {
+    if let (Some(line), false) = (block.fastcgi_pass_line, block.has_fastcgi_include) {
+        warnings.push(lint_warning(
+            line,
+            "warning",
+            "fastcgi_pass without `include fastcgi_params` in the same block".to_string(),
+        ));
+    }
+
+    if let (Some(line), false) = (block.ssl_certificate_line, block.has_ssl_certificate_key) {
+        warnings.push(lint_warning(
+            line,
+            "error",
+            "ssl_certificate without a matching ssl_certificate_key".to_string(),
+        ));
+    }
+}
+
+fn lint_config(content: &str) -> Vec<NginxLintWarning> {
+    let mut warnings = Vec::new();
+    let mut stack: Vec<LintBlock> = vec![LintBlock {
+        name: "main".to_string(),
+        line: 0,
+        fastcgi_pass_line: None,
+        has_fastcgi_include: false,
+        ssl_certificate_line: None,
+        has_ssl_certificate_key: false,
+    }];
+
+    // Statements may span lines, so text is carried over until a `;`, `{`
+    // or `}` ends it; `location / { try_files ...; }` on one line is split
+    // the same way as the multi-line form. Like nginx, quoted strings may
+    // hold any of those characters, and `#` only starts a comment at the
+    // beginning of a token.
+    let mut statement = String::new();
+    let mut statement_line = 1;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        if statement.trim().is_empty() {
+            statement_line = idx + 1;
+        }
+
+        for ch in line.chars() {
+            let line_no = statement_line;
+
+            if let Some(open) = quote {
+                statement.push(ch);
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == open {
+                    quote = None;
+                }
+                continue;
+            }
+
+            match ch {
+                '"' | '\'' => {
+                    quote = Some(ch);
+                    statement.push(ch);
+                }
+                '#' if statement.is_empty() || statement.ends_with(char::is_whitespace) => break,
+                '{' | '}' | ';' => {
+                    let text = statement.trim().to_string();
+                    statement.clear();
+
+                    let in_value_block = stack
+                        .last()
+                        .is_some_and(|b| NON_DIRECTIVE_BLOCKS.contains(&b.name.as_str()));
+
+                    let mut words = text.split_whitespace();
+                    let directive = words.next().unwrap_or_default().to_string();
+                    let args: Vec<&str> = words.collect();
+
+                    if !directive.is_empty() && !in_value_block {
+                        if !KNOWN_DIRECTIVES.contains(&directive.as_str()) {
+                            warnings.push(lint_warning(
+                                line_no,
+                                "warning",
+                                format!("Unknown directive: {}", directive),
+                            ));
+                        }
+
+                        if let Some(block) = stack.last_mut() {
+                            match directive.as_str() {
+                                "fastcgi_pass" => block.fastcgi_pass_line = Some(line_no),
+                                "include" if args.iter().any(|a| a.contains("fastcgi")) => {
+                                    block.has_fastcgi_include = true
+                                }
+                                "ssl_certificate" => block.ssl_certificate_line = Some(line_no),
+                                "ssl_certificate_key" => block.has_ssl_certificate_key = true,
+                                _ => {}
+                            }
+                        }
+
+                        if directive == "listen"
+                            && args.iter().any(|a| a.ends_with("443"))
+                            && !args.iter().any(|a| *a == "ssl" || *a == "quic")
+                        {
+                            warnings.push(lint_warning(
+                                line_no,
+                                "warning",
+                                "listen 443 without the ssl parameter".to_string(),
+                            ));
+                        }
+                    }
+
+                    match ch {
+                        '{' => stack.push(LintBlock {
+                            name: directive,
+                            line: line_no,
+                            fastcgi_pass_line: None,
+                            has_fastcgi_include: false,
+                            ssl_certificate_line: None,
+                            has_ssl_certificate_key: false,
+                        }),
+                        '}' => {
+                            if stack.len() > 1 {
+                                if let Some(block) = stack.pop() {
+                                    close_lint_block(block, &mut warnings);
+                                }
+                            } else {
+                                warnings.push(lint_warning(
+                                    line_no,
+                                    "error",
+                                    "Unexpected `}` with no open block".to_string(),
+                                ));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                _ => statement.push(ch),
+            }
+
+            if statement.is_empty() {
+                statement_line = idx + 1;
+            }
+        }
+
+        statement.push(' ');
+    }
+
+    if quote.is_some() {
+        warnings.push(lint_warning(
+            statement_line,
+            "error",
+            "Unterminated quoted string".to_string(),
+        ));
+    } else if !statement.trim().is_empty() {
+        warnings.push(lint_warning(
+            statement_line,
+            "error",
+            "Statement is missing a terminating `;`".to_string(),
+        ));
+    }
+
+    while stack.len() > 1 {
+        if let Some(block) = stack.pop() {
+            warnings.push(lint_warning(
+                block.line,
+                "error",
+                format!("Unclosed `{{` for {} block", block.name),
+            ));
+            close_lint_block(block, &mut warnings);
+        }
+    }
+
+    if let Some(main) = stack.pop() {
+        close_lint_block(main, &mut warnings);
+    }
+
+    warnings.sort_by_key(|w| w.line);
+    warnings
+}
+
+#[tauri::command]
+pub async fn list_vhosts() -> Result<Vec<NginxVhost>, String> {
+    load_vhosts()
+}
+
+#[tauri::command]
+pub async fn get_vhost(id: String) -> Result<NginxVhost, String> {
+    let vhosts = load_vhosts()?;
+    vhosts
+        .into_iter()
+        .find(|v| v.id == id)
+        .ok_or_else(|| format!("Vhost not found: {}", id))
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_vhost(
+    server_name: String,
+    document_root: String,
+    php_enabled: bool,
+    ssl_enabled: bool,
+    ssl_cert_path: Option<String>,
+    ssl_key_path: Option<String>,
+    acme_challenge_root: Option<String>,
+    proxy_target: Option<String>,
+    websocket_enabled: Option<bool>,
+    websocket_path: Option<String>,
+    redirects: Option<Vec<RedirectRule>>,
+) -> Result<NginxVhost, String> {
+    let redirects = redirects.unwrap_or_default();
+    for redirect in &redirects {
+        validate_redirect(redirect)?;
+    }
+
+    let mut vhosts = load_vhosts()?;
+
+    // Check for duplicate server names
+    if vhosts.iter().any(|v| v.server_name == server_name) {
+        return Err(format!("Vhost with server name '{}' already exists", server_name));
+    }
+
+    let nginx_conf_dir = get_nginx_conf_dir();
+    fs::create_dir_all(&nginx_conf_dir)
+        .map_err(|e| format!("Failed to create nginx conf directory: {}", e))?;
+
+    let id = Uuid::new_v4().to_string();
+    let config_filename = format!("{}.conf", server_name.replace('.', "_"));
+    let config_path = nginx_conf_dir.join(&config_filename);
+
+    let vhost = NginxVhost {
+        id,
+        server_name,
+        document_root,
+        php_enabled,
+        ssl_enabled,
+        ssl_cert_path,
+        ssl_key_path,
+        config_path: config_path.to_string_lossy().to_string(),
+        allowlist: Vec::new(),
+        denylist: Vec::new(),
+        maps: Vec::new(),
+        acme_challenge_root,
+        proxy_target,
+        proxy_keepalive: None,
+        react_app: None,
+        enabled: true,
+        rate_limit: None,
+        websocket_enabled: websocket_enabled.unwrap_or(false),
+        websocket_path: websocket_path.unwrap_or_else(default_websocket_path),
+        security_headers: None,
+        compression: None,
+        basic_auth: None,
+        ip_restrictions: Vec::new(),
+        upstreams: Vec::new(),
+        client_max_body_size: None,
+        fastcgi: None,
+        redirects,
+        cors: None,
+        fpm_upstream: None,
+        fpm_pool: None,
+        rewrites: Vec::new(),
+        error_pages: HashMap::new(),
+        includes: Vec::new(),
+    };
+    validate_websocket(&vhost)?;
+
+    // Generate and write config file
+    let config_content = generate_vhost_config_content(&vhost);
+    fs::write(&config_path, &config_content)
+        .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+
+    vhosts.push(vhost.clone());
+    save_vhosts(&vhosts)?;
+    write_shared_maps(&vhosts)?;
+
+    Ok(vhost)
+}
+
+// Copies every setting of an existing vhost under a new server name. Names
+// that must be unique across the http context (rate limit zones, upstreams)
+// get a suffix, and maps are left out because their variables can only be
+// defined once.
+#[tauri::command]
+pub async fn clone_vhost(source_id: String, new_server_name: String) -> Result<NginxVhost, String> {
+    let mut vhosts = load_vhosts()?;
+
+    if vhosts.iter().any(|v| v.server_name == new_server_name) {
+        return Err(format!("Vhost with server name '{}' already exists", new_server_name));
+    }
+
+    let source = vhosts
+        .iter()
+        .find(|v| v.id == source_id)
+        .ok_or_else(|| format!("Vhost not found: {}", source_id))?;
+
+    let suffix = new_server_name.replace(['.', '-', '*'], "_");
+    let mut vhost = source.clone();
+    vhost.id = Uuid::new_v4().to_string();
+    vhost.server_name = new_server_name;
+    vhost.maps = Vec::new();
+    // Challenge directories are per server name
+    vhost.acme_challenge_root = None;
+
+    let config_path = get_nginx_conf_dir().join(format!("{}.conf", vhost.server_name.replace('.', "_")));
+    vhost.config_path = config_path.to_string_lossy().to_string();
+    if !vhost.enabled {
+        vhost.config_path.push_str(DISABLED_SUFFIX);
+    }
+
+    if let Some(rate_limit) = &mut vhost.rate_limit {
+        rate_limit.zone_name = format!("{}_{}", rate_limit.zone_name, suffix);
+    }
+    for upstream in &mut vhost.upstreams {
+        let renamed = format!("{}_{}", upstream.name, suffix);
+        if vhost.proxy_target.as_deref() == Some(upstream.name.as_str()) {
+            vhost.proxy_target = Some(renamed.clone());
+        }
+        upstream.name = renamed;
+    }
+
+    fs::write(&vhost.config_path, generate_vhost_config_content(&vhost))
+        .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+    write_htpasswd(&vhost)?;
+
+    vhosts.push(vhost.clone());
+    save_vhosts(&vhosts)?;
+    write_shared_rate_limits(&vhosts)?;
+
+    Ok(vhost)
+}
+
+#[tauri::command]
+pub async fn generate_react_nginx_config(
+    app_name: String,
+    static_port: u16,
+    api_port: u16,
+    build_dir: String,
+) -> Result<String, String> {
+    if static_port == api_port {
+        return Err("The static and API ports must differ".to_string());
+    }
+
+    let server_name = if app_name.ends_with(".sig") {
+        app_name
+    } else {
+        format!("{}.sig", app_name)
+    };
+
+    let mut vhost = create_vhost(server_name, build_dir, false, false, None, None, None, None, None, None, None).await?;
+    vhost.react_app = Some(ReactAppConfig { static_port, api_port });
+    update_vhost(vhost.clone()).await?;
+
+    Ok(generate_vhost_config_content(&vhost))
+}
+
+#[tauri::command]
+pub async fn update_vhost(vhost: NginxVhost) -> Result<NginxVhost, String> {
+    if let Some(keepalive) = &vhost.proxy_keepalive {
+        if vhost.proxy_target.is_none() {
+            return Err("Keepalive requires a proxy target".to_string());
+        }
+        if keepalive.connections == 0 {
+            return Err("Keepalive connections must be greater than zero".to_string());
+        }
+    }
+    validate_websocket(&vhost)?;
+    if let Some(basic_auth) = &vhost.basic_auth {
+        validate_basic_auth(basic_auth)?;
+    }
+    for restriction in &vhost.ip_restrictions {
+        validate_ip_restriction(restriction)?;
+    }
+    validate_body_and_buffers(&vhost)?;
+    for redirect in &vhost.redirects {
+        validate_redirect(redirect)?;
+    }
+    if let Some(cors) = &vhost.cors {
+        validate_cors(cors)?;
+    }
+    for rule in &vhost.rewrites {
+        validate_rewrite(rule)?;
+    }
+    validate_error_pages(&vhost.error_pages)?;
+    validate_includes(&vhost)?;
+    if let Some(upstream) = &vhost.fpm_upstream {
+        if upstream.is_empty() || upstream.contains(|c: char| c.is_whitespace() || c == ';') {
+            return Err(format!("Invalid PHP-FPM upstream: {}", upstream));
+        }
+    }
+    if let Some(name) = &vhost.fpm_pool {
+        if vhost.fpm_upstream.is_some() {
+            return Err("Set either a PHP-FPM pool or an upstream, not both".to_string());
+        }
+        let pools = php::load_pools()?;
+        let pool = pools
+            .iter()
+            .find(|p| &p.name == name)
+            .ok_or_else(|| format!("PHP-FPM pool not found: {}", name))?;
+        php::pool_fastcgi_address(pool)?;
+    }
+
+    let mut vhosts = load_vhosts()?;
+
+    let idx = vhosts
+        .iter()
+        .position(|v| v.id == vhost.id)
+        .ok_or_else(|| format!("Vhost not found: {}", vhost.id))?;
+
+    if let Some(rate_limit) = &vhost.rate_limit {
+        validate_rate_limit(&vhost.id, rate_limit, &vhosts)?;
+    }
+    validate_upstreams(&vhost, &vhosts)?;
+
+    // Regenerate config file
+    let config_content = generate_vhost_config_content(&vhost);
+    fs::write(&vhost.config_path, &config_content)
+        .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+    write_htpasswd(&vhost)?;
+
+    vhosts[idx] = vhost.clone();
+    save_vhosts(&vhosts)?;
+    write_shared_maps(&vhosts)?;
+    write_shared_rate_limits(&vhosts)?;
+
+    Ok(vhost)
+}
+
+#[tauri::command]
+pub async fn delete_vhost(id: String) -> Result<(), String> {
+    let mut vhosts = load_vhosts()?;
+
+    let idx = vhosts
+        .iter()
+        .position(|v| v.id == id)
+        .ok_or_else(|| format!("Vhost not found: {}", id))?;
+
+    // Delete config file
+    let config_path = PathBuf::from(&vhosts[idx].config_path);
+    if config_path.exists() {
+        fs::remove_file(&config_path)
+            .map_err(|e| format!("Failed to delete vhost config: {}", e))?;
+    }
+
+    let htpasswd_path = get_htpasswd_path(&vhosts[idx]);
+    if htpasswd_path.exists() {
+        fs::remove_file(&htpasswd_path)
+            .map_err(|e| format!("Failed to delete htpasswd file: {}", e))?;
+    }
+
+    vhosts.remove(idx);
+    save_vhosts(&vhosts)?;
+    write_shared_maps(&vhosts)?;
+    write_shared_rate_limits(&vhosts)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_vhost_enabled(id: String, enabled: bool) -> Result<NginxVhost, String> {
+    let mut vhosts = load_vhosts()?;
+
+    let vhost = vhosts
+        .iter_mut()
+        .find(|v| v.id == id)
+        .ok_or_else(|| format!("Vhost not found: {}", id))?;
+
+    if vhost.enabled == enabled {
+        return Ok(vhost.clone());
+    }
+
+    let current_path = vhost.config_path.clone();
+    let new_path = if enabled {
+        current_path
+            .strip_suffix(DISABLED_SUFFIX)
+            .map(String::from)
+            .ok_or_else(|| format!("Unexpected config path for disabled vhost: {}", current_path))?
+    } else {
+        format!("{}{}", current_path, DISABLED_SUFFIX)
+    };
+
+    fs::rename(&current_path, &new_path)
+        .map_err(|e| format!("Failed to rename vhost config: {}", e))?;
+
+    vhost.config_path = new_path;
+    vhost.enabled = enabled;
+    let updated = vhost.clone();
+
+    save_vhosts(&vhosts)?;
+    reload_nginx().await?;
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn update_ip_access_rules(
+    vhost_id: String,
+    allowlist: Vec<String>,
+    denylist: Vec<String>,
+) -> Result<(), String> {
+    for rule in allowlist.iter().chain(denylist.iter()) {
+        validate_ip_rule(rule)?;
+    }
+
+    let mut vhosts = load_vhosts()?;
+
+    let vhost = vhosts
+        .iter_mut()
+        .find(|v| v.id == vhost_id)
+        .ok_or_else(|| format!("Vhost not found: {}", vhost_id))?;
+
+    // Patch only the access rules so manual edits elsewhere are preserved
+    let config = fs::read_to_string(&vhost.config_path)
+        .map_err(|e| format!("Failed to read vhost config: {}", e))?;
+    let block = generate_access_rules_block(&allowlist, &denylist);
+    let updated = replace_access_rules(&config, &block)?;
+
+    fs::write(&vhost.config_path, updated)
+        .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+
+    vhost.allowlist = allowlist;
+    vhost.denylist = denylist;
+    save_vhosts(&vhosts)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn generate_acme_challenge_dir(vhost_id: String) -> Result<String, String> {
+    let vhosts = load_vhosts()?;
+
+    let vhost = vhosts
+        .iter()
+        .find(|v| v.id == vhost_id)
+        .ok_or_else(|| format!("Vhost not found: {}", vhost_id))?;
+
+    // nginx appends the request URI to `root`, so the challenge files live
+    // under .well-known/acme-challenge inside the returned directory
+    let root = get_acme_root_dir().join(&vhost.server_name);
+    let challenge_dir = root.join(".well-known").join("acme-challenge");
+    fs::create_dir_all(&challenge_dir)
+        .map_err(|e| format!("Failed to create ACME challenge directory: {}", e))?;
+
+    Ok(root.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn add_vhost_map(vhost_id: String, map: NginxMap) -> Result<NginxVhost, String> {
+    validate_map_variable(&map.variable)?;
+
+    let mut vhosts = load_vhosts()?;
+
+    // nginx rejects two maps defining the same variable
+    if let Some(other) = vhosts
+        .iter()
+        .find(|v| v.id != vhost_id && v.maps.iter().any(|m| m.variable == map.variable))
+    {
+        return Err(format!(
+            "Variable '{}' is already mapped by {}",
+            map.variable, other.server_name
+        ));
+    }
+
+    let vhost = vhosts
+        .iter_mut()
+        .find(|v| v.id == vhost_id)
+        .ok_or_else(|| format!("Vhost not found: {}", vhost_id))?;
+
+    vhost.maps.retain(|m| m.variable != map.variable);
+    vhost.maps.push(map);
+    let updated = vhost.clone();
+
+    save_vhosts(&vhosts)?;
+    write_shared_maps(&vhosts)?;
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn remove_vhost_map(vhost_id: String, variable: String) -> Result<NginxVhost, String> {
+    let mut vhosts = load_vhosts()?;
+
+    let vhost = vhosts
+        .iter_mut()
+        .find(|v| v.id == vhost_id)
+        .ok_or_else(|| format!("Vhost not found: {}", vhost_id))?;
+
+    let before = vhost.maps.len();
+    vhost.maps.retain(|m| m.variable != variable);
+    if vhost.maps.len() == before {
+        return Err(format!("Map not found: {}", variable));
+    }
+    let updated = vhost.clone();
+
+    save_vhosts(&vhosts)?;
+    write_shared_maps(&vhosts)?;
+
+    Ok(updated)
+}
+
+async fn apply_vhost_rate_limit(id: String, rate_limit: Option<RateLimitConfig>) -> Result<NginxVhost, String> {
+    let mut vhosts = load_vhosts()?;
+
+    if let Some(rate_limit) = &rate_limit {
+        validate_rate_limit(&id, rate_limit, &vhosts)?;
+    }
+
+    let vhost = vhosts
+        .iter_mut()
+        .find(|v| v.id == id)
+        .ok_or_else(|| format!("Vhost not found: {}", id))?;
+
+    vhost.rate_limit = rate_limit;
+    fs::write(&vhost.config_path, generate_vhost_config_content(vhost))
+        .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+    let updated = vhost.clone();
 
-    config
+    save_vhosts(&vhosts)?;
+    write_shared_rate_limits(&vhosts)?;
+    reload_nginx().await?;
+
+    Ok(updated)
 }
 
 #[tauri::command]
-pub async fn list_vhosts() -> Result<Vec<NginxVhost>, String> {
-    load_vhosts()
+pub async fn set_vhost_rate_limit(id: String, config: RateLimitConfig) -> Result<NginxVhost, String> {
+    apply_vhost_rate_limit(id, Some(config)).await
 }
 
 #[tauri::command]
-pub async fn get_vhost(id: String) -> Result<NginxVhost, String> {
-    let vhosts = load_vhosts()?;
-    vhosts
-        .into_iter()
-        .find(|v| v.id == id)
-        .ok_or_else(|| format!("Vhost not found: {}", id))
+pub async fn remove_vhost_rate_limit(id: String) -> Result<NginxVhost, String> {
+    apply_vhost_rate_limit(id, None).await
 }
 
 #[tauri::command]
-pub async fn create_vhost(
-    server_name: String,
-    document_root: String,
-    php_enabled: bool,
-    ssl_enabled: bool,
-    ssl_cert_path: Option<String>,
-    ssl_key_path: Option<String>,
-) -> Result<NginxVhost, String> {
-    let mut vhosts = load_vhosts()?;
-
-    // Check for duplicate server names
-    if vhosts.iter().any(|v| v.server_name == server_name) {
-        return Err(format!("Vhost with server name '{}' already exists", server_name));
-    }
+pub async fn generate_security_headers_preset(level: SecurityLevel) -> Result<SecurityHeadersConfig, String> {
+    Ok(match level {
+        SecurityLevel::Strict => SecurityHeadersConfig {
+            hsts_max_age: Some(63_072_000),
+            csp: Some("default-src 'self'; object-src 'none'; frame-ancestors 'none'; base-uri 'self'".to_string()),
+            x_frame_options: "DENY".to_string(),
+            x_content_type_options: true,
+            referrer_policy: "no-referrer".to_string(),
+        },
+        SecurityLevel::Moderate => SecurityHeadersConfig {
+            hsts_max_age: Some(31_536_000),
+            csp: Some("default-src 'self' 'unsafe-inline' 'unsafe-eval' data: blob:".to_string()),
+            x_frame_options: "SAMEORIGIN".to_string(),
+            x_content_type_options: true,
+            referrer_policy: "strict-origin-when-cross-origin".to_string(),
+        },
+        // Short HSTS so a dev certificate change doesn't lock the browser out
+        SecurityLevel::Permissive => SecurityHeadersConfig {
+            hsts_max_age: Some(300),
+            csp: None,
+            x_frame_options: "SAMEORIGIN".to_string(),
+            x_content_type_options: true,
+            referrer_policy: "no-referrer-when-downgrade".to_string(),
+        },
+    })
+}
 
-    let nginx_conf_dir = get_nginx_conf_dir();
-    fs::create_dir_all(&nginx_conf_dir)
-        .map_err(|e| format!("Failed to create nginx conf directory: {}", e))?;
+// Maps the usual status codes to /<code>.html for the pages that exist in
+// the document root
+#[tauri::command]
+pub async fn generate_default_error_pages(document_root: String) -> Result<HashMap<u16, String>, String> {
+    Ok(DEFAULT_ERROR_PAGE_CODES
+        .iter()
+        .map(|code| (*code, format!("/{}.html", code)))
+        .filter(|(_, path)| PathBuf::from(&document_root).join(path.trim_start_matches('/')).is_file())
+        .collect())
+}
 
-    let id = Uuid::new_v4().to_string();
-    let config_filename = format!("{}.conf", server_name.replace('.', "_"));
-    let config_path = nginx_conf_dir.join(&config_filename);
+#[tauri::command]
+pub async fn list_nginx_includes() -> Result<Vec<NginxInclude>, String> {
+    SnippetManager::load()
+}
 
-    let vhost = NginxVhost {
-        id,
-        server_name,
-        document_root,
-        php_enabled,
-        ssl_enabled,
-        ssl_cert_path,
-        ssl_key_path,
-        config_path: config_path.to_string_lossy().to_string(),
+#[tauri::command]
+pub async fn create_nginx_include(
+    name: String,
+    filename: String,
+    content: String,
+) -> Result<NginxInclude, String> {
+    let include = NginxInclude {
+        id: Uuid::new_v4().to_string(),
+        name,
+        filename,
+        content,
     };
 
-    // Generate and write config file
-    let config_content = generate_vhost_config_content(&vhost);
-    fs::write(&config_path, &config_content)
-        .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+    let mut includes = SnippetManager::load()?;
+    SnippetManager::validate(&include, &includes)?;
+    SnippetManager::write_snippet(&include)?;
 
-    vhosts.push(vhost.clone());
-    save_vhosts(&vhosts)?;
+    includes.push(include.clone());
+    SnippetManager::save(&includes)?;
 
-    Ok(vhost)
+    Ok(include)
 }
 
+// Vhosts reference includes by name and path, so a rename rewrites them
 #[tauri::command]
-pub async fn update_vhost(vhost: NginxVhost) -> Result<NginxVhost, String> {
-    let mut vhosts = load_vhosts()?;
+pub async fn update_nginx_include(include: NginxInclude) -> Result<NginxInclude, String> {
+    let mut includes = SnippetManager::load()?;
+    SnippetManager::validate(&include, &includes)?;
 
-    let idx = vhosts
+    let idx = includes
         .iter()
-        .position(|v| v.id == vhost.id)
-        .ok_or_else(|| format!("Vhost not found: {}", vhost.id))?;
+        .position(|i| i.id == include.id)
+        .ok_or_else(|| format!("Include not found: {}", include.id))?;
+    let previous = includes[idx].clone();
 
-    // Regenerate config file
-    let config_content = generate_vhost_config_content(&vhost);
-    fs::write(&vhost.config_path, &config_content)
-        .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+    if previous.filename != include.filename {
+        SnippetManager::remove_snippet(&previous)?;
+    }
+    SnippetManager::write_snippet(&include)?;
 
-    vhosts[idx] = vhost.clone();
-    save_vhosts(&vhosts)?;
+    includes[idx] = include.clone();
+    SnippetManager::save(&includes)?;
 
-    Ok(vhost)
+    let mut vhosts = load_vhosts()?;
+    let mut changed = false;
+    for vhost in vhosts.iter_mut().filter(|v| v.includes.contains(&previous.name)) {
+        for name in vhost.includes.iter_mut().filter(|n| **n == previous.name) {
+            *name = include.name.clone();
+        }
+        fs::write(&vhost.config_path, generate_vhost_config_content(vhost))
+            .map_err(|e| format!("Failed to write vhost config: {}", e))?;
+        changed = true;
+    }
+    if changed {
+        save_vhosts(&vhosts)?;
+    }
+
+    Ok(include)
 }
 
 #[tauri::command]
-pub async fn delete_vhost(id: String) -> Result<(), String> {
-    let mut vhosts = load_vhosts()?;
+pub async fn delete_nginx_include(id: String) -> Result<(), String> {
+    let mut includes = SnippetManager::load()?;
 
-    let idx = vhosts
+    let idx = includes
         .iter()
-        .position(|v| v.id == id)
-        .ok_or_else(|| format!("Vhost not found: {}", id))?;
+        .position(|i| i.id == id)
+        .ok_or_else(|| format!("Include not found: {}", id))?;
 
-    // Delete config file
-    let config_path = PathBuf::from(&vhosts[idx].config_path);
-    if config_path.exists() {
-        fs::remove_file(&config_path)
-            .map_err(|e| format!("Failed to delete vhost config: {}", e))?;
+    let name = &includes[idx].name;
+    if let Some(vhost) = load_vhosts()?.iter().find(|v| v.includes.contains(name)) {
+        return Err(format!("Include '{}' is still used by {}", name, vhost.server_name));
     }
 
-    vhosts.remove(idx);
-    save_vhosts(&vhosts)?;
+    SnippetManager::remove_snippet(&includes[idx])?;
+    includes.remove(idx);
+    SnippetManager::save(&includes)?;
 
     Ok(())
 }
 
+#[tauri::command]
+pub async fn evaluate_tls_config(vhost_id: String) -> Result<TlsGradeReport, String> {
+    let vhost = get_vhost(vhost_id).await?;
+
+    if !vhost.ssl_enabled {
+        return Err(format!("SSL is not enabled for {}", vhost.server_name));
+    }
+
+    // Read the file on disk so hand edits are graded too
+    let config = fs::read_to_string(&vhost.config_path)
+        .map_err(|e| format!("Failed to read vhost config: {}", e))?;
+
+    Ok(grade_tls_config(&config))
+}
+
 #[tauri::command]
 pub async fn get_vhost_config(id: String) -> Result<String, String> {
     let vhost = get_vhost(id).await?;
@@ -233,13 +2316,21 @@ pub async fn get_vhost_config(id: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-pub async fn save_vhost_config(id: String, content: String) -> Result<(), String> {
+pub async fn lint_nginx_config_content(content: String) -> Result<Vec<NginxLintWarning>, String> {
+    Ok(lint_config(&content))
+}
+
+#[tauri::command]
+pub async fn save_vhost_config(id: String, content: String) -> Result<Vec<NginxLintWarning>, String> {
     let vhost = get_vhost(id).await?;
 
+    // Lint findings are advisory; the config is saved either way
+    let warnings = lint_config(&content);
+
     fs::write(&vhost.config_path, content)
         .map_err(|e| format!("Failed to write vhost config: {}", e))?;
 
-    Ok(())
+    Ok(warnings)
 }
 
 #[tauri::command]
@@ -262,6 +2353,209 @@ pub async fn test_nginx_config() -> Result<NginxTestResult, String> {
         success: output.status.success(),
         output: if output.status.success() { stdout } else { stderr.clone() },
         errors,
+        warnings: brotli_preflight()?,
+    })
+}
+
+// Brotli directives are unknown to nginx builds without ngx_brotli, which
+// `nginx -V` lists among its configure arguments
+fn brotli_preflight() -> Result<Vec<String>, String> {
+    let requested: Vec<String> = load_vhosts()?
+        .into_iter()
+        .filter(|v| v.enabled && v.compression.as_ref().is_some_and(|c| c.brotli_enabled))
+        .map(|v| v.server_name)
+        .collect();
+
+    if requested.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let output = Command::new("docker")
+        .args(["exec", "signalforge-nginx", "nginx", "-V"])
+        .output()
+        .map_err(|e| format!("Failed to query nginx modules: {}", e))?;
+
+    // nginx -V prints to stderr
+    let modules = String::from_utf8_lossy(&output.stderr);
+    if modules.contains("brotli") {
+        return Ok(Vec::new());
+    }
+
+    Ok(requested
+        .into_iter()
+        .map(|server_name| {
+            format!(
+                "{} requests Brotli compression but nginx was built without the ngx_brotli module",
+                server_name
+            )
+        })
+        .collect())
+}
+
+// `combined`: $remote_addr - $remote_user [$time_local] "$request" $status
+// $body_bytes_sent "$http_referer" "$http_user_agent"
+fn parse_access_log_lines(content: &str) -> Result<Vec<AccessLogEntry>, String> {
+    let line = Regex::new(
+        r#"^(\S+) \S+ \S+ \[([^\]]+)\] "(\S+) (\S+)[^"]*" (\d{3}) (\d+|-) "([^"]*)" "([^"]*)""#,
+    )
+    .map_err(|e| format!("Failed to build access log pattern: {}", e))?;
+
+    // Anything else in the log (e.g. container startup output) is skipped
+    Ok(content
+        .lines()
+        .filter_map(|l| line.captures(l))
+        .map(|c| AccessLogEntry {
+            remote_addr: c[1].to_string(),
+            time_local: c[2].to_string(),
+            method: c[3].to_string(),
+            uri: c[4].to_string(),
+            status: c[5].parse().unwrap_or_default(),
+            bytes_sent: c[6].parse().unwrap_or(0),
+            referer: c[7].to_string(),
+            user_agent: c[8].to_string(),
+        })
+        .collect())
+}
+
+// The official nginx image links its logs to the container's stdout, in
+// which case the lines have to come from `docker logs` instead
+fn read_container_log_tail(log_path: &str, limit: usize) -> Result<String, String> {
+    let output = Command::new("docker")
+        .args(["exec", "signalforge-nginx", "sh", "-c", r#"[ -L "$1" ] && exit 3; tail -n "$2" "$1""#, "sh"])
+        .args([log_path, &limit.to_string()])
+        .output()
+        .map_err(|e| format!("Failed to read nginx log: {}", e))?;
+
+    if output.status.code() == Some(3) {
+        let output = Command::new("docker")
+            .args(["logs", "--tail", &limit.to_string(), "signalforge-nginx"])
+            .output()
+            .map_err(|e| format!("Failed to read nginx log: {}", e))?;
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+// Reads the host file when `log_path` exists locally, otherwise the path
+// inside the nginx container. Returns at most `limit` of the newest entries.
+#[tauri::command]
+pub async fn parse_nginx_access_log(log_path: String, limit: usize) -> Result<Vec<AccessLogEntry>, String> {
+    let content = if PathBuf::from(&log_path).is_file() {
+        fs::read_to_string(&log_path).map_err(|e| format!("Failed to read nginx log: {}", e))?
+    } else {
+        read_container_log_tail(&log_path, limit)?
+    };
+
+    let mut entries = parse_access_log_lines(&content)?;
+    if entries.len() > limit {
+        entries.drain(..entries.len() - limit);
+    }
+
+    Ok(entries)
+}
+
+// Log locations as configured in the running nginx, from the full config
+// dump of `nginx -T`
+#[tauri::command]
+pub async fn get_nginx_log_paths() -> Result<NginxLogPaths, String> {
+    let output = Command::new("docker")
+        .args(["exec", "signalforge-nginx", "nginx", "-T"])
+        .output()
+        .map_err(|e| format!("Failed to read nginx config: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let config = String::from_utf8_lossy(&output.stdout);
+    let paths = |directive: &str| {
+        let mut paths: Vec<String> = directive_values(&config, directive)
+            .into_iter()
+            .filter_map(|value| value.split_whitespace().next().map(String::from))
+            .filter(|path| path != "off")
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    };
+
+    Ok(NginxLogPaths {
+        access_logs: paths("access_log"),
+        error_logs: paths("error_log"),
+    })
+}
+
+// Mirrors nginx's handling of the rules in `location /`. Every rule carries a
+// flag and all four end rewrite processing, so the first match decides.
+fn simulate_rewrites(rules: &[RewriteRule], uri: &str) -> Result<(String, Option<usize>), String> {
+    let mut path = uri.split('?').next().unwrap_or_default().to_string();
+
+    for (idx, rule) in rules.iter().enumerate() {
+        let re = Regex::new(&rule.pattern)
+            .map_err(|e| format!("Cannot evaluate rewrite pattern {}: {}", rule.pattern, e))?;
+
+        if re.is_match(&path) {
+            path = re.replace(&path, rule.replacement.as_str()).to_string();
+            return Ok((path, Some(idx)));
+        }
+    }
+
+    Ok((path, None))
+}
+
+// Validates and reloads the config, then requests `test_uri` from the vhost
+// through the local nginx without following redirects
+#[tauri::command]
+pub async fn test_rewrite_rules(vhost_id: String, test_uri: String) -> Result<RewriteTestResult, String> {
+    if !test_uri.starts_with('/') {
+        return Err(format!("Test URI must start with '/': {}", test_uri));
+    }
+
+    let vhost = get_vhost(vhost_id).await?;
+
+    let test = test_nginx_config().await?;
+    if !test.success {
+        return Err(format!("nginx config test failed: {}", test.errors.join("; ")));
+    }
+    reload_nginx().await?;
+
+    let (resolved_path, matched_rule) = simulate_rewrites(&vhost.rewrites, &test_uri)?;
+
+    let scheme = if vhost.ssl_enabled { "https" } else { "http" };
+    let port = if vhost.ssl_enabled { 443 } else { 80 };
+    let server_name = vhost.server_name.trim_start_matches("*.");
+
+    // Local certificates aren't in reqwest's trust store
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve(server_name, std::net::SocketAddr::from(([127, 0, 0, 1], port)))
+        .danger_accept_invalid_certs(true)
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let response = client
+        .get(format!("{}://{}{}", scheme, server_name, test_uri))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to request {}: {}", test_uri, e))?;
+
+    let location = response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+
+    Ok(RewriteTestResult {
+        resolved_path,
+        matched_rule,
+        status: response.status().as_u16(),
+        location,
     })
 }
 
@@ -307,3 +2601,377 @@ pub async fn generate_default_nginx_config() -> Result<String, String> {
 }
 "#.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_stores;
+
+    fn vhost(server_name: &str) -> NginxVhost {
+        NginxVhost {
+            id: format!("vhost-{}", server_name),
+            server_name: server_name.to_string(),
+            document_root: "/var/www/html/public".to_string(),
+            php_enabled: false,
+            ssl_enabled: false,
+            ssl_cert_path: None,
+            ssl_key_path: None,
+            config_path: format!("/tmp/{}.conf", server_name),
+            allowlist: Vec::new(),
+            denylist: Vec::new(),
+            maps: Vec::new(),
+            acme_challenge_root: None,
+            proxy_target: None,
+            proxy_keepalive: None,
+            react_app: None,
+            enabled: true,
+            rate_limit: None,
+            websocket_enabled: false,
+            websocket_path: default_websocket_path(),
+            security_headers: None,
+            compression: None,
+            basic_auth: None,
+            ip_restrictions: Vec::new(),
+            upstreams: Vec::new(),
+            client_max_body_size: None,
+            fastcgi: None,
+            redirects: Vec::new(),
+            cors: None,
+            fpm_upstream: None,
+            fpm_pool: None,
+            rewrites: Vec::new(),
+            error_pages: HashMap::new(),
+            includes: Vec::new(),
+        }
+    }
+
+    fn map(variable: &str, mappings: &[(&str, &str)]) -> NginxMap {
+        NginxMap {
+            variable: variable.to_string(),
+            default: "0".to_string(),
+            mappings: mappings.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    const HAND_EDITED: &str = "server {
+    listen 80;
+    server_name shop.sig;
+    # custom: keep me
+    client_max_body_size 64m;
+    root /var/www/shop/public;
+    index index.php;
+}
+";
+
+    // Everything outside the marked section, which must survive rule updates
+    fn outside_access_rules(config: &str) -> String {
+        let begin = config.find(ACCESS_RULES_BEGIN).unwrap();
+        let end = config.find(ACCESS_RULES_END).unwrap() + ACCESS_RULES_END.len();
+        let line_start = config[..begin].rfind('\n').map_or(0, |i| i + 1);
+        format!("{}{}", &config[..line_start], &config[end + 1..])
+    }
+
+    #[test]
+    fn access_rules_are_inserted_before_root() {
+        let block = generate_access_rules_block(&["10.0.0.0/8".to_string()], &["10.0.0.66".to_string()]);
+
+        let config = replace_access_rules(HAND_EDITED, &block).unwrap();
+
+        let expected = format!(
+            "    client_max_body_size 64m;
+    {}
+    deny 10.0.0.66;
+    allow 10.0.0.0/8;
+    deny all;
+    {}
+    root /var/www/shop/public;
+",
+            ACCESS_RULES_BEGIN, ACCESS_RULES_END
+        );
+        assert!(config.contains(&expected));
+        assert_eq!(outside_access_rules(&config), HAND_EDITED);
+    }
+
+    #[test]
+    fn access_rules_update_only_changes_their_section() {
+        let first = generate_access_rules_block(&["192.168.1.0/24".to_string()], &[]);
+        let config = replace_access_rules(HAND_EDITED, &first).unwrap();
+
+        let second = generate_access_rules_block(&[], &["203.0.113.7".to_string()]);
+        let updated = replace_access_rules(&config, &second).unwrap();
+
+        assert_eq!(outside_access_rules(&updated), outside_access_rules(&config));
+        assert!(updated.contains("    deny 203.0.113.7;\n"));
+        assert!(!updated.contains("192.168.1.0/24"));
+        assert!(!updated.contains("deny all;"));
+        assert_eq!(updated.matches(ACCESS_RULES_BEGIN).count(), 1);
+    }
+
+    #[test]
+    fn access_rules_land_in_the_https_block_of_an_acme_vhost() {
+        let mut shop = vhost("shop.sig");
+        shop.ssl_enabled = true;
+        shop.ssl_cert_path = Some("/certs/shop.sig.crt".to_string());
+        shop.ssl_key_path = Some("/certs/shop.sig.key".to_string());
+        shop.acme_challenge_root = Some("/var/www/acme".to_string());
+        let block = generate_access_rules_block(&["10.0.0.0/8".to_string()], &[]);
+
+        let config = replace_access_rules(&generate_vhost_config_content(&shop), &block).unwrap();
+
+        let https = config.find("listen 443 ssl").unwrap();
+        let challenge = config.find("location ^~ /.well-known/acme-challenge/ {").unwrap();
+        let rules = config.find("    allow 10.0.0.0/8;\n").unwrap();
+        assert!(challenge < https && https < rules);
+        assert_eq!(config.matches(ACCESS_RULES_BEGIN).count(), 1);
+    }
+
+    #[test]
+    fn access_rules_skip_roots_nested_in_locations() {
+        let config = "server {
+    listen 80;
+    location ^~ /.well-known/acme-challenge/ {
+        root /var/www/acme;
+    }
+    root /var/www/shop/public;
+}
+";
+        let block = generate_access_rules_block(&[], &["203.0.113.7".to_string()]);
+
+        let updated = replace_access_rules(config, &block).unwrap();
+
+        let expected = format!(
+            "    }}\n    {}\n    deny 203.0.113.7;\n    {}\n    root /var/www/shop/public;\n",
+            ACCESS_RULES_BEGIN, ACCESS_RULES_END
+        );
+        assert!(updated.contains(&expected));
+    }
+
+    #[test]
+    fn access_rules_need_a_root_directive() {
+        let block = generate_access_rules_block(&[], &[]);
+        assert!(replace_access_rules("server {\n    listen 80;\n}\n", &block).is_err());
+    }
+
+    #[test]
+    fn maps_config_lists_every_vhost_map_sorted() {
+        let mut shop = vhost("shop.sig");
+        shop.maps.push(map("http_user_agent", &[("~*mobile", "1"), ("~*bot", "2")]));
+        let mut blog = vhost("blog.sig");
+        blog.maps.push(map("cookie_theme", &[("dark", "dark")]));
+
+        assert_eq!(
+            generate_maps_config_content(&[shop, blog, vhost("plain.sig")]),
+            r#"# Signalforge Dev - shared map directives
+
+# shop.sig
+map $http_user_agent $mapped_http_user_agent {
+    default "0";
+    "~*bot" "2";
+    "~*mobile" "1";
+}
+
+# blog.sig
+map $cookie_theme $mapped_cookie_theme {
+    default "0";
+    "dark" "dark";
+}
+"#
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn add_vhost_map_writes_the_shared_file() {
+        let _stores = isolated_stores();
+        let shop = vhost("shop.sig");
+        let blog = vhost("blog.sig");
+        save_vhosts(&[shop.clone(), blog.clone()]).unwrap();
+
+        add_vhost_map(shop.id.clone(), map("http_user_agent", &[("~*mobile", "1")]))
+            .await
+            .unwrap();
+
+        let shared = fs::read_to_string(get_maps_config_path()).unwrap();
+        assert!(shared.contains("# shop.sig\nmap $http_user_agent $mapped_http_user_agent {\n"));
+        assert!(!shared.contains("blog.sig"));
+
+        // The same variable can't be mapped twice across vhosts
+        let duplicate = add_vhost_map(blog.id.clone(), map("http_user_agent", &[])).await;
+        assert!(duplicate.unwrap_err().contains("already mapped by shop.sig"));
+
+        remove_vhost_map(shop.id, "http_user_agent".to_string()).await.unwrap();
+        assert!(!fs::read_to_string(get_maps_config_path()).unwrap().contains("map $"));
+    }
+
+    const MODERN_TLS: &str = r#"server {
+    listen 443 ssl;
+    http2 on;
+    server_name shop.sig;
+    ssl_certificate /var/lib/signalforge/certs/shop.sig.pem;
+    ssl_certificate_key /var/lib/signalforge/certs/shop.sig-key.pem;
+    ssl_protocols TLSv1.2 TLSv1.3;
+    ssl_ciphers ECDHE-ECDSA-AES128-GCM-SHA256:ECDHE-RSA-AES128-GCM-SHA256:ECDHE-ECDSA-CHACHA20-POLY1305;
+    ssl_prefer_server_ciphers off;
+    add_header Strict-Transport-Security "max-age=63072000" always;
+}
+"#;
+
+    #[test]
+    fn modern_tls_config_grades_a_plus() {
+        let report = grade_tls_config(MODERN_TLS);
+
+        assert_eq!(report.grade, "A+");
+        assert!(report.findings.is_empty());
+        assert!(report.recommendations.is_empty());
+    }
+
+    #[test]
+    fn legacy_protocols_cap_the_grade() {
+        let config = MODERN_TLS.replace("TLSv1.2 TLSv1.3", "TLSv1 TLSv1.1 TLSv1.2");
+        let report = grade_tls_config(&config);
+
+        assert_eq!(report.grade, TLS_GRADES[3]);
+        assert!(report.findings.iter().any(|f| f.severity == "critical" && f.message.contains("TLSv1.0")));
+    }
+
+    #[test]
+    fn weak_ciphers_and_missing_hsts_are_reported() {
+        let config = MODERN_TLS
+            .replace("ECDHE-ECDSA-AES128-GCM-SHA256", "RC4-SHA")
+            .replace("    add_header Strict-Transport-Security \"max-age=63072000\" always;\n", "");
+        let report = grade_tls_config(&config);
+
+        assert_eq!(report.grade, TLS_GRADES[3]);
+        assert!(report.findings.iter().any(|f| f.message.contains("RC4-SHA")));
+        assert!(report.findings.iter().any(|f| f.message.contains("HSTS")));
+    }
+
+    #[test]
+    fn keepalive_pool_is_declared_before_the_server_block() {
+        let mut proxied = vhost("api.sig");
+        proxied.proxy_target = Some("127.0.0.1:3000".to_string());
+        proxied.proxy_keepalive =
+            Some(NginxKeepalive { connections: 32, timeout_seconds: 60, requests: 1000 });
+
+        let config = generate_vhost_config_content(&proxied);
+
+        let upstream = config.find("upstream sig_api_sig {").expect("upstream block");
+        let server = config.find("server {").expect("server block");
+        assert!(upstream < server);
+        assert!(config[upstream..server].contains(
+            "    server 127.0.0.1:3000;\n    keepalive 32;\n    keepalive_timeout 60s;\n    keepalive_requests 1000;\n"
+        ));
+        assert!(config.contains("proxy_pass http://sig_api_sig;"));
+        assert!(config.contains("proxy_http_version 1.1;"));
+        assert!(config.contains("proxy_set_header Connection \"\";"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn disabling_a_vhost_renames_its_config_and_reloads() {
+        let mut stores = isolated_stores();
+        let docker_log = stores.root.path().join("docker.log");
+        stores.fake_command("docker", &format!("echo \"$@\" >> {}", docker_log.display()));
+        let conf_dir = stores.root.path().join("conf.d");
+        fs::create_dir_all(&conf_dir).unwrap();
+        let conf = conf_dir.join("shop.sig.conf");
+        fs::write(&conf, "server {}\n").unwrap();
+        let mut shop = vhost("shop.sig");
+        shop.config_path = conf.to_string_lossy().to_string();
+        save_vhosts(&[shop.clone()]).unwrap();
+
+        let disabled = set_vhost_enabled(shop.id.clone(), false).await.unwrap();
+
+        let disabled_conf = conf_dir.join("shop.sig.conf.disabled");
+        assert!(!disabled.enabled);
+        assert_eq!(disabled.config_path, disabled_conf.to_string_lossy());
+        assert!(!conf.exists());
+        assert_eq!(fs::read_to_string(&disabled_conf).unwrap(), "server {}\n");
+        assert_eq!(load_vhosts().unwrap()[0].config_path, disabled.config_path);
+
+        let enabled = set_vhost_enabled(shop.id.clone(), true).await.unwrap();
+
+        assert!(enabled.enabled);
+        assert_eq!(enabled.config_path, shop.config_path);
+        assert!(conf.exists() && !disabled_conf.exists());
+        assert_eq!(
+            fs::read_to_string(&docker_log).unwrap(),
+            "exec signalforge-nginx nginx -s reload\nexec signalforge-nginx nginx -s reload\n"
+        );
+    }
+
+    fn lint(content: &str) -> Vec<(usize, String, String)> {
+        lint_config(content)
+            .into_iter()
+            .map(|w| (w.line, w.severity, w.message))
+            .collect()
+    }
+
+    #[test]
+    fn generated_configs_lint_clean() {
+        let mut shop = vhost("shop.sig");
+        shop.php_enabled = true;
+        shop.ssl_enabled = true;
+        shop.ssl_cert_path = Some("/certs/shop.sig.crt".to_string());
+        shop.ssl_key_path = Some("/certs/shop.sig.key".to_string());
+
+        assert_eq!(lint(&generate_vhost_config_content(&shop)), Vec::new());
+    }
+
+    #[test]
+    fn lint_reports_unbalanced_braces() {
+        let config = "server {\n    listen 80;\n    location / {\n        index index.php;\n}\n";
+        assert_eq!(
+            lint(config),
+            vec![(1, "error".to_string(), "Unclosed `{` for server block".to_string())]
+        );
+
+        assert_eq!(
+            lint("server {\n}\n}\n"),
+            vec![(3, "error".to_string(), "Unexpected `}` with no open block".to_string())]
+        );
+    }
+
+    #[test]
+    fn lint_reports_directive_problems() {
+        let config = "server {
+    listen 443;
+    server_nme shop.sig;
+    ssl_certificate /certs/shop.sig.crt;
+
+    location ~ \\.php$ {
+        fastcgi_pass php:9000;
+    }
+}
+";
+
+        assert_eq!(
+            lint(config),
+            vec![
+                (2, "warning".to_string(), "listen 443 without the ssl parameter".to_string()),
+                (3, "warning".to_string(), "Unknown directive: server_nme".to_string()),
+                (4, "error".to_string(), "ssl_certificate without a matching ssl_certificate_key".to_string()),
+                (7, "warning".to_string(), "fastcgi_pass without `include fastcgi_params` in the same block".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_splits_statements_on_one_line_and_ignores_quoted_braces() {
+        let config = "server { location / { try_files $uri /index.php; } }\n\
+                      map $host $x { default \"{;}\"; }\n\
+                      server { add_header X-Note \"a # b\"; } # done\n";
+
+        assert_eq!(lint(config), Vec::new());
+    }
+
+    #[test]
+    fn validate_ip_rule_accepts_addresses_and_ranges() {
+        for rule in ["127.0.0.1", "10.0.0.0/8", "::1", "2001:db8::/32", "0.0.0.0/0"] {
+            assert!(validate_ip_rule(rule).is_ok(), "{}", rule);
+        }
+        for rule in ["not-an-ip", "10.0.0.0/33", "::1/129", "10.0.0.1/abc", ""] {
+            assert!(validate_ip_rule(rule).is_err(), "{}", rule);
+        }
+    }
+}
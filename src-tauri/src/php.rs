@@ -0,0 +1,271 @@
+use crate::nginx;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PmMode {
+    Static,
+    Dynamic,
+    OnDemand,
+}
+
+impl PmMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            PmMode::Static => "static",
+            PmMode::Dynamic => "dynamic",
+            PmMode::OnDemand => "ondemand",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PhpFpmPool {
+    pub name: String,
+    // Address or socket the pool listens on, e.g. "9001" or "/run/php/app.sock"
+    pub listen: String,
+    pub pm_mode: PmMode,
+    pub max_children: u32,
+    pub start_servers: u32,
+    pub min_spare: u32,
+    pub max_spare: u32,
+}
+
+fn get_pools_file() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("signalforge-dev")
+        .join("fpm_pools.json")
+}
+
+// Pool files sit next to the nginx configs so both can be mounted together
+fn get_pool_config_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("SignalforgeData")
+        .join("nginx")
+        .join("php-fpm.d")
+}
+
+fn get_pool_config_path(name: &str) -> PathBuf {
+    get_pool_config_dir().join(format!("{}.conf", name))
+}
+
+// Where fpm containers see the pool directory. The official images only
+// include php-fpm.d/*.conf, so a mounted include file pulls the pools in.
+const CONTAINER_POOL_DIR: &str = "/usr/local/etc/php-fpm.d/signalforge";
+const CONTAINER_POOL_INCLUDE: &str = "/usr/local/etc/php-fpm.d/zz-signalforge.conf";
+
+fn get_pool_include_path() -> PathBuf {
+    get_pool_config_dir().with_file_name("php-fpm-signalforge.conf")
+}
+
+// Bind mounts for a php-fpm service, in compose short syntax
+pub fn fpm_pool_volumes() -> Vec<String> {
+    vec![
+        format!("{}:{}:ro", get_pool_config_dir().to_string_lossy(), CONTAINER_POOL_DIR),
+        format!("{}:{}:ro", get_pool_include_path().to_string_lossy(), CONTAINER_POOL_INCLUDE),
+    ]
+}
+
+// Docker creates missing bind mount sources as directories, so both have to
+// exist before an fpm service starts
+pub fn ensure_pool_mounts() -> Result<(), String> {
+    fs::create_dir_all(get_pool_config_dir())
+        .map_err(|e| format!("Failed to create PHP-FPM pool directory: {}", e))?;
+
+    let include = format!("include={}/*.conf\n", CONTAINER_POOL_DIR);
+    if fs::read_to_string(get_pool_include_path()).ok().as_deref() != Some(include.as_str()) {
+        fs::write(get_pool_include_path(), include)
+            .map_err(|e| format!("Failed to write PHP-FPM pool include: {}", e))?;
+    }
+    Ok(())
+}
+
+// nginx reaches pools through the compose php service, so only TCP
+// listeners work; a socket stays inside the php container
+pub fn pool_fastcgi_address(pool: &PhpFpmPool) -> Result<String, String> {
+    let (host, port) = pool.listen.rsplit_once(':').unwrap_or(("", &pool.listen));
+    let loopback = matches!(host, "127.0.0.1" | "localhost" | "[::1]");
+    if pool.listen.starts_with('/') || loopback || port.parse::<u16>().is_err() {
+        return Err(format!(
+            "Pool '{}' listens on {}, which nginx can't reach",
+            pool.name, pool.listen
+        ));
+    }
+    Ok(format!("php:{}", port))
+}
+
+pub(crate) fn load_pools() -> Result<Vec<PhpFpmPool>, String> {
+    let path = get_pools_file();
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read PHP-FPM pools: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse PHP-FPM pools: {}", e))
+}
+
+fn save_pools(pools: &[PhpFpmPool]) -> Result<(), String> {
+    let path = get_pools_file();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(pools)
+        .map_err(|e| format!("Failed to serialize PHP-FPM pools: {}", e))?;
+
+    fs::write(&path, content)
+        .map_err(|e| format!("Failed to write PHP-FPM pools: {}", e))
+}
+
+fn validate_pool(pool: &PhpFpmPool) -> Result<(), String> {
+    if pool.name.is_empty()
+        || !pool.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!("Invalid pool name: {}", pool.name));
+    }
+    if pool.listen.is_empty() || pool.listen.contains(char::is_whitespace) {
+        return Err(format!("Invalid listen address: {}", pool.listen));
+    }
+    // The image's own www pool already holds 9000
+    if pool.listen.rsplit(':').next() == Some("9000") {
+        return Err("Port 9000 is used by the default www pool".to_string());
+    }
+    if pool.max_children == 0 {
+        return Err("max_children must be greater than zero".to_string());
+    }
+
+    // PHP-FPM refuses to start a dynamic pool with inconsistent spare settings
+    if pool.pm_mode == PmMode::Dynamic
+        && !(pool.min_spare <= pool.start_servers
+            && pool.start_servers <= pool.max_spare
+            && pool.max_spare <= pool.max_children)
+    {
+        return Err(
+            "Dynamic pools need min_spare <= start_servers <= max_spare <= max_children".to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+pub fn generate_fpm_pool_config(pool: &PhpFpmPool) -> String {
+    let mut config = String::from("; Signalforge Dev - PHP-FPM pool\n");
+
+    config.push_str(&format!("[{}]\n", pool.name));
+    config.push_str("user = www-data\n");
+    config.push_str("group = www-data\n");
+    config.push_str(&format!("listen = {}\n", pool.listen));
+    config.push_str(&format!("pm = {}\n", pool.pm_mode.as_str()));
+    config.push_str(&format!("pm.max_children = {}\n", pool.max_children));
+
+    // The spare server settings only apply to dynamic pools
+    if pool.pm_mode == PmMode::Dynamic {
+        config.push_str(&format!("pm.start_servers = {}\n", pool.start_servers));
+        config.push_str(&format!("pm.min_spare_servers = {}\n", pool.min_spare));
+        config.push_str(&format!("pm.max_spare_servers = {}\n", pool.max_spare));
+    }
+
+    config
+}
+
+fn write_pool_config(pool: &PhpFpmPool) -> Result<(), String> {
+    ensure_pool_mounts()?;
+
+    fs::write(get_pool_config_path(&pool.name), generate_fpm_pool_config(pool))
+        .map_err(|e| format!("Failed to write PHP-FPM pool config: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_fpm_pools() -> Result<Vec<PhpFpmPool>, String> {
+    load_pools()
+}
+
+#[tauri::command]
+pub async fn create_fpm_pool(pool: PhpFpmPool) -> Result<PhpFpmPool, String> {
+    validate_pool(&pool)?;
+
+    let mut pools = load_pools()?;
+
+    if pools.iter().any(|p| p.name == pool.name) {
+        return Err(format!("Pool '{}' already exists", pool.name));
+    }
+    if pools.iter().any(|p| p.listen == pool.listen) {
+        return Err(format!("Another pool already listens on {}", pool.listen));
+    }
+
+    write_pool_config(&pool)?;
+
+    pools.push(pool.clone());
+    save_pools(&pools)?;
+
+    Ok(pool)
+}
+
+#[tauri::command]
+pub async fn update_fpm_pool(pool: PhpFpmPool) -> Result<PhpFpmPool, String> {
+    validate_pool(&pool)?;
+
+    let mut pools = load_pools()?;
+
+    if pools.iter().any(|p| p.name != pool.name && p.listen == pool.listen) {
+        return Err(format!("Another pool already listens on {}", pool.listen));
+    }
+
+    let idx = pools
+        .iter()
+        .position(|p| p.name == pool.name)
+        .ok_or_else(|| format!("Pool not found: {}", pool.name))?;
+
+    write_pool_config(&pool)?;
+
+    pools[idx] = pool.clone();
+    save_pools(&pools)?;
+
+    // The pool's listen address is baked into fastcgi_pass
+    let vhosts = nginx::load_vhosts()?;
+    let users: Vec<&nginx::NginxVhost> = vhosts
+        .iter()
+        .filter(|v| v.fpm_pool.as_deref() == Some(pool.name.as_str()))
+        .collect();
+    nginx::regenerate_vhost_configs(&users)?;
+
+    Ok(pool)
+}
+
+#[tauri::command]
+pub async fn delete_fpm_pool(name: String) -> Result<(), String> {
+    let mut pools = load_pools()?;
+
+    let idx = pools
+        .iter()
+        .position(|p| p.name == name)
+        .ok_or_else(|| format!("Pool not found: {}", name))?;
+
+    if let Some(vhost) = nginx::load_vhosts()?
+        .iter()
+        .find(|v| v.fpm_pool.as_deref() == Some(name.as_str()))
+    {
+        return Err(format!("Pool '{}' is still used by {}", name, vhost.server_name));
+    }
+
+    let config_path = get_pool_config_path(&name);
+    if config_path.exists() {
+        fs::remove_file(&config_path)
+            .map_err(|e| format!("Failed to delete PHP-FPM pool config: {}", e))?;
+    }
+
+    pools.remove(idx);
+    save_pools(&pools)?;
+
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use crate::config;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+// Environment keys containing any of these are treated as secrets
+const SENSITIVE_KEY_MARKERS: [&str; 4] = ["PASSWORD", "SECRET", "KEY", "TOKEN"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct EncryptedSecret {
+    nonce: String,
+    ciphertext: String,
+}
+
+type SecretStore = HashMap<String, EncryptedSecret>;
+
+pub fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_uppercase();
+    SENSITIVE_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+fn get_secrets_file(project_id: &str) -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("signalforge-dev")
+        .join(project_id)
+        .join("secrets.json")
+}
+
+// Key used before each project got a random one; only read to migrate
+fn legacy_key(project_id: &str) -> Key<Aes256Gcm> {
+    let digest = Sha256::new()
+        .chain_update(b"signalforge-dev project secrets:")
+        .chain_update(project_id.as_bytes())
+        .finalize();
+    *Key::<Aes256Gcm>::from_slice(&digest)
+}
+
+fn keychain_entry(project_id: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(config::KEYCHAIN_SERVICE, &format!("secrets:{}", project_id))
+        .map_err(|e| format!("Failed to open keychain: {}", e))
+}
+
+// The project's random AES key, kept in the OS keychain. Generated on first
+// use, at which point any store written under the legacy key is re-encrypted.
+fn project_key(project_id: &str) -> Result<Key<Aes256Gcm>, String> {
+    let entry = keychain_entry(project_id)?;
+
+    match entry.get_password() {
+        Ok(hex) => {
+            let bytes = from_hex(&hex)?;
+            if bytes.len() != 32 {
+                return Err("Invalid secrets key in keychain".to_string());
+            }
+            Ok(*Key::<Aes256Gcm>::from_slice(&bytes))
+        }
+        Err(keyring::Error::NoEntry) => {
+            let key = Aes256Gcm::generate_key(OsRng);
+            let migrated = reencrypt_legacy_store(project_id, &key)?;
+
+            entry
+                .set_password(&to_hex(&key))
+                .map_err(|e| format!("Failed to save secrets key to keychain: {}", e))?;
+            if !migrated.is_empty() {
+                save_store(project_id, &migrated)?;
+            }
+
+            Ok(key)
+        }
+        Err(e) => Err(format!("Failed to read secrets key from keychain: {}", e)),
+    }
+}
+
+// The project's store re-encrypted from the legacy key to `key`. Not saved
+// here so the caller can persist the new key first.
+fn reencrypt_legacy_store(project_id: &str, key: &Key<Aes256Gcm>) -> Result<SecretStore, String> {
+    let legacy = legacy_key(project_id);
+
+    load_store(project_id)?
+        .into_iter()
+        .map(|(name, secret)| {
+            let value = decrypt_with(&legacy, &secret)?;
+            Ok((name, encrypt_with(key, &value)?))
+        })
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(text: &str) -> Result<Vec<u8>, String> {
+    // Checked up front so the byte slicing below always lands on char
+    // boundaries
+    if !text.is_ascii() || !text.len().is_multiple_of(2) {
+        return Err("Invalid hex string".to_string());
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&text[i..i + 2], 16).map_err(|e| format!("Invalid hex string: {}", e))
+        })
+        .collect()
+}
+
+fn load_store(project_id: &str) -> Result<SecretStore, String> {
+    let path = get_secrets_file(project_id);
+
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read secrets: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse secrets: {}", e))
+}
+
+fn save_store(project_id: &str, store: &SecretStore) -> Result<(), String> {
+    let path = get_secrets_file(project_id);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create secrets directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+
+    fs::write(&path, content)
+        .map_err(|e| format!("Failed to write secrets: {}", e))
+}
+
+pub fn set_secret(project_id: &str, key_name: &str, value: &str) -> Result<(), String> {
+    let secret = encrypt_with(&project_key(project_id)?, value)?;
+
+    let mut store = load_store(project_id)?;
+    store.insert(key_name.to_string(), secret);
+    save_store(project_id, &store)
+}
+
+pub fn get_secret(project_id: &str, key_name: &str) -> Result<String, String> {
+    let store = load_store(project_id)?;
+    let secret = store
+        .get(key_name)
+        .ok_or_else(|| format!("Secret not found: {}", key_name))?;
+
+    decrypt_with(&project_key(project_id)?, secret)
+}
+
+// All secrets for a project, decrypted, for passing to docker compose
+pub fn get_all_secrets(project_id: &str) -> Result<HashMap<String, String>, String> {
+    let store = load_store(project_id)?;
+    // Projects without secrets never touch the keychain
+    if store.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let key = project_key(project_id)?;
+    // Reload in case fetching the key migrated the store
+    load_store(project_id)?
+        .iter()
+        .map(|(name, secret)| decrypt_with(&key, secret).map(|value| (name.clone(), value)))
+        .collect()
+}
+
+pub fn remove_all_secrets(project_id: &str) -> Result<(), String> {
+    let dir = get_secrets_file(project_id)
+        .parent()
+        .map(PathBuf::from)
+        .ok_or_else(|| "Invalid secrets path".to_string())?;
+
+    // The key is only created alongside a secrets file, so a project
+    // without one has nothing in the keychain either
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    fs::remove_dir_all(&dir)
+        .map_err(|e| format!("Failed to remove secrets: {}", e))?;
+
+    match keychain_entry(project_id)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove secrets key from keychain: {}", e)),
+    }
+}
+
+fn encrypt_with(key: &Key<Aes256Gcm>, value: &str) -> Result<EncryptedSecret, String> {
+    let cipher = Aes256Gcm::new(key);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, value.as_bytes())
+        .map_err(|e| format!("Failed to encrypt secret: {}", e))?;
+
+    Ok(EncryptedSecret {
+        nonce: to_hex(&nonce),
+        ciphertext: to_hex(&ciphertext),
+    })
+}
+
+fn decrypt_with(key: &Key<Aes256Gcm>, secret: &EncryptedSecret) -> Result<String, String> {
+    let cipher = Aes256Gcm::new(key);
+    let nonce_bytes = from_hex(&secret.nonce)?;
+    if nonce_bytes.len() != 12 {
+        return Err("Invalid secret nonce".to_string());
+    }
+
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce_bytes), from_hex(&secret.ciphertext)?.as_ref())
+        .map_err(|e| format!("Failed to decrypt secret: {}", e))?;
+
+    String::from_utf8(plaintext).map_err(|e| format!("Failed to decode secret: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_stores;
+
+    fn test_key() -> Key<Aes256Gcm> {
+        *Key::<Aes256Gcm>::from_slice(&[7u8; 32])
+    }
+
+    #[test]
+    fn encrypted_secret_round_trips() {
+        let secret = encrypt_with(&test_key(), "s3cr3t pässword").unwrap();
+
+        assert_eq!(decrypt_with(&test_key(), &secret).unwrap(), "s3cr3t pässword");
+    }
+
+    #[test]
+    fn wrong_key_fails_to_decrypt() {
+        let secret = encrypt_with(&test_key(), "s3cr3t").unwrap();
+        let other = *Key::<Aes256Gcm>::from_slice(&[8u8; 32]);
+
+        let err = decrypt_with(&other, &secret).unwrap_err();
+        assert!(err.starts_with("Failed to decrypt secret"), "{}", err);
+    }
+
+    #[test]
+    fn from_hex_rejects_malformed_input() {
+        assert_eq!(from_hex("00ff7a").unwrap(), vec![0x00, 0xff, 0x7a]);
+        assert!(from_hex("abc").is_err());
+        assert!(from_hex("zz").is_err());
+        // Two bytes, even length, but slicing at 1 would split the char
+        assert!(from_hex("é").is_err());
+        assert!(from_hex("éa0").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn legacy_store_is_reencrypted_with_the_project_key() {
+        let _stores = isolated_stores();
+        let legacy = legacy_key("proj-1");
+        let mut store = SecretStore::new();
+        store.insert("DB_PASSWORD".to_string(), encrypt_with(&legacy, "hunter2").unwrap());
+        save_store("proj-1", &store).unwrap();
+
+        let migrated = reencrypt_legacy_store("proj-1", &test_key()).unwrap();
+
+        assert_eq!(decrypt_with(&test_key(), &migrated["DB_PASSWORD"]).unwrap(), "hunter2");
+        assert!(decrypt_with(&legacy, &migrated["DB_PASSWORD"]).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn migrating_without_a_store_yields_nothing() {
+        let _stores = isolated_stores();
+
+        assert!(reencrypt_legacy_store("proj-2", &test_key()).unwrap().is_empty());
+    }
+}
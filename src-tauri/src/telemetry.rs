@@ -0,0 +1,59 @@
+use crate::config;
+use serde::Serialize;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TelemetryEvent {
+    pub command: String,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub app_version: String,
+    pub os: String,
+}
+
+// Runs a command body and reports its outcome. Only the static command name,
+// success flag and duration leave the machine - never arguments, paths or domains.
+pub async fn track<T, F>(command: &'static str, body: F) -> Result<T, String>
+where
+    F: Future<Output = Result<T, String>>,
+{
+    let started = Instant::now();
+    let result = body.await;
+    record(command, result.is_ok(), started.elapsed());
+    result
+}
+
+// Fire-and-forget: sends the event in the background when telemetry is enabled
+pub fn record(command: &'static str, success: bool, duration: Duration) {
+    tauri::async_runtime::spawn(async move {
+        let Ok(app_config) = config::get_app_config().await else {
+            return;
+        };
+
+        if !app_config.telemetry_enabled {
+            return;
+        }
+
+        let Some(endpoint) = app_config.telemetry_endpoint else {
+            return;
+        };
+
+        let event = TelemetryEvent {
+            command: command.to_string(),
+            success,
+            duration_ms: duration.as_millis() as u64,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+        };
+
+        let Ok(client) = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+        else {
+            return;
+        };
+
+        let _ = client.post(&endpoint).json(&event).send().await;
+    });
+}
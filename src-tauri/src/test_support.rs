@@ -0,0 +1,62 @@
+// Tests that go through the JSON stores point HOME and the config dir at a
+// temp directory. The lock keeps them from seeing each other's stores, since
+// the environment is shared by every test thread.
+static STORE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+pub struct IsolatedStores {
+    pub root: tempfile::TempDir,
+    previous: Vec<(&'static str, Option<std::ffi::OsString>)>,
+    _guard: std::sync::MutexGuard<'static, ()>,
+}
+
+impl IsolatedStores {
+    pub fn home(&self) -> std::path::PathBuf {
+        self.root.path().join("home")
+    }
+
+    // Puts a shell script named `name` first on PATH, so code that shells out
+    // to it runs the script instead. PATH is restored on drop.
+    #[cfg(unix)]
+    pub fn fake_command(&mut self, name: &str, script: &str) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = self.root.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let command = bin.join(name);
+        std::fs::write(&command, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&command, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        if !self.previous.iter().any(|(var, _)| *var == "PATH") {
+            let path = std::env::var_os("PATH");
+            let mut dirs = vec![bin];
+            dirs.extend(std::env::split_paths(path.as_deref().unwrap_or_default()));
+            std::env::set_var("PATH", std::env::join_paths(dirs).unwrap());
+            self.previous.push(("PATH", path));
+        }
+    }
+}
+
+impl Drop for IsolatedStores {
+    fn drop(&mut self) {
+        for (name, value) in self.previous.drain(..) {
+            match value {
+                Some(value) => std::env::set_var(name, value),
+                None => std::env::remove_var(name),
+            }
+        }
+    }
+}
+
+// dirs only honours XDG_CONFIG_HOME on Linux, so callers are Linux-only
+pub fn isolated_stores() -> IsolatedStores {
+    let guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let root = tempfile::tempdir().unwrap();
+
+    let mut previous = Vec::new();
+    for (name, dir) in [("HOME", "home"), ("XDG_CONFIG_HOME", "config")] {
+        previous.push((name, std::env::var_os(name)));
+        std::env::set_var(name, root.path().join(dir));
+    }
+
+    IsolatedStores { root, previous, _guard: guard }
+}
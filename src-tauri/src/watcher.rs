@@ -0,0 +1,255 @@
+use crate::commands::AppState;
+use crate::compose;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+// Default quiet period before buffered events are emitted
+const DEFAULT_DEBOUNCE_MS: u64 = 200;
+
+// Active watchers keyed by watch ID; dropping one stops the watch
+pub type FileWatchers = HashMap<String, RecommendedWatcher>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileChangeEvent {
+    pub watch_id: String,
+    pub paths: Vec<String>,
+    pub kinds: Vec<String>,
+}
+
+// Collects raw notify events and consolidates them once no new event has
+// arrived for `window`. Every new event pushes the deadline back.
+pub struct DebounceBuffer {
+    window: Duration,
+    paths: BTreeSet<String>,
+    kinds: BTreeSet<String>,
+    deadline: Option<Instant>,
+}
+
+impl DebounceBuffer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            paths: BTreeSet::new(),
+            kinds: BTreeSet::new(),
+            deadline: None,
+        }
+    }
+
+    pub fn push(&mut self, event: notify::Event) {
+        let kind = match event.kind {
+            EventKind::Create(_) => "create",
+            EventKind::Modify(_) => "modify",
+            EventKind::Remove(_) => "delete",
+            _ => return,
+        };
+
+        for path in event.paths {
+            // Deleted files can no longer be canonicalized
+            let path = fs::canonicalize(&path).unwrap_or(path);
+            self.paths.insert(path.to_string_lossy().to_string());
+        }
+
+        self.kinds.insert(kind.to_string());
+        self.deadline = Some(Instant::now() + self.window);
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    pub fn take(&mut self, watch_id: &str) -> Option<FileChangeEvent> {
+        self.deadline = None;
+
+        if self.paths.is_empty() {
+            return None;
+        }
+
+        Some(FileChangeEvent {
+            watch_id: watch_id.to_string(),
+            paths: std::mem::take(&mut self.paths).into_iter().collect(),
+            kinds: std::mem::take(&mut self.kinds).into_iter().collect(),
+        })
+    }
+}
+
+async fn run_debounce_loop(
+    app: AppHandle,
+    watch_id: String,
+    rx: mpsc::UnboundedReceiver<notify::Event>,
+    window: Duration,
+) {
+    debounce_events(rx, &watch_id, window, |change| {
+        let _ = app.emit("file-change", change);
+    })
+    .await;
+}
+
+// Runs events through a DebounceBuffer until the channel closes, handing each
+// consolidated change to `emit`
+async fn debounce_events(
+    mut rx: mpsc::UnboundedReceiver<notify::Event>,
+    watch_id: &str,
+    window: Duration,
+    mut emit: impl FnMut(FileChangeEvent),
+) {
+    let mut buffer = DebounceBuffer::new(window);
+
+    loop {
+        let deadline = buffer.deadline();
+        let flush = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now));
+
+        tokio::select! {
+            event = rx.recv() => match event {
+                Some(event) => buffer.push(event),
+                None => break,
+            },
+            _ = flush, if deadline.is_some() => {
+                if let Some(change) = buffer.take(watch_id) {
+                    emit(change);
+                }
+            }
+        }
+    }
+}
+
+async fn start_watch(
+    app: AppHandle,
+    state: &AppState,
+    watch_id: String,
+    path: PathBuf,
+    debounce_ms: Option<u64>,
+) -> Result<String, String> {
+    if !path.exists() {
+        return Err(format!("Path does not exist: {}", path.display()));
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    watcher
+        .watch(&path, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+
+    let window = Duration::from_millis(debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS));
+    tauri::async_runtime::spawn(run_debounce_loop(app, watch_id.clone(), rx, window));
+
+    // Replacing an existing watcher drops it, which ends its debounce loop
+    state
+        .file_watchers
+        .lock()
+        .await
+        .insert(watch_id.clone(), watcher);
+
+    Ok(watch_id)
+}
+
+#[tauri::command]
+pub async fn start_file_watch(
+    app: AppHandle,
+    path: String,
+    debounce_ms: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    start_watch(app, &state, path.clone(), PathBuf::from(&path), debounce_ms).await
+}
+
+#[tauri::command]
+pub async fn watch_project(
+    app: AppHandle,
+    project_id: String,
+    debounce_ms: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let project = compose::get_project(project_id.clone()).await?;
+    start_watch(app, &state, project_id, PathBuf::from(&project.root_path), debounce_ms).await
+}
+
+#[tauri::command]
+pub async fn stop_file_watch(watch_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .file_watchers
+        .lock()
+        .await
+        .remove(&watch_id)
+        .map(|_| ())
+        .ok_or_else(|| format!("No active watch: {}", watch_id))
+}
+
+#[tauri::command]
+pub async fn list_file_watches(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let watchers = state.file_watchers.lock().await;
+    let mut ids: Vec<String> = watchers.keys().cloned().collect();
+    ids.sort();
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, DataChange, ModifyKind};
+
+    fn modify(path: &str) -> notify::Event {
+        notify::Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content)))
+            .add_path(PathBuf::from(path))
+    }
+
+    #[tokio::test]
+    async fn rapid_events_produce_one_emission() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (emitted_tx, mut emitted_rx) = mpsc::unbounded_channel();
+
+        let task = tokio::spawn(async move {
+            debounce_events(rx, "watch-1", Duration::from_millis(100), |change| {
+                let _ = emitted_tx.send(change);
+            })
+            .await;
+        });
+
+        for i in 0..10 {
+            tx.send(modify(&format!("/project/src/file{}.php", i % 3))).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        drop(tx);
+        task.await.unwrap();
+
+        let change = emitted_rx.recv().await.unwrap();
+        assert_eq!(change.watch_id, "watch-1");
+        assert_eq!(change.paths.len(), 3);
+        assert_eq!(change.kinds, vec!["modify"]);
+        assert!(emitted_rx.recv().await.is_none());
+    }
+
+    #[test]
+    fn buffer_merges_kinds_and_ignores_access_events() {
+        let mut buffer = DebounceBuffer::new(Duration::from_millis(50));
+        assert!(buffer.take("watch-1").is_none());
+
+        let access = EventKind::Access(notify::event::AccessKind::Any);
+        buffer.push(notify::Event::new(access).add_path("/a".into()));
+        assert!(buffer.deadline().is_none());
+
+        let create = EventKind::Create(CreateKind::File);
+        buffer.push(notify::Event::new(create).add_path("/project/new.php".into()));
+        buffer.push(modify("/project/new.php"));
+        assert!(buffer.deadline().is_some());
+
+        let change = buffer.take("watch-1").unwrap();
+        assert_eq!(change.paths, vec!["/project/new.php"]);
+        assert_eq!(change.kinds, vec!["create", "modify"]);
+        assert!(buffer.deadline().is_none());
+        assert!(buffer.take("watch-1").is_none());
+    }
+}
@@ -13,6 +13,19 @@ export interface ContainerInfo {
   state: string
   created: number
   ports: PortMapping[]
+  // Filled in only when list_containers is called with includeLimits
+  memory_limit?: number | null
+  cpu_quota?: number | null
+  cpu_period?: number | null
+}
+
+// Only the inspect response has these, so they're loaded per container
+export interface ResourceLimits {
+  memory_bytes: number | null
+  memory_swap_bytes: number | null
+  cpu_quota: number | null
+  cpu_period: number | null
+  pids_limit: number | null
 }
 
 export interface PortMapping {
@@ -79,6 +92,7 @@ export const useDockerStore = defineStore('docker', () => {
   const containers = ref<ContainerInfo[]>([])
   const dockerInfo = ref<DockerInfo | null>(null)
   const containerStats = ref<Map<string, ContainerStats>>(new Map())
+  const resourceLimits = ref<Map<string, ResourceLimits>>(new Map())
   const isConnected = ref(false)
   const loading = ref(false)
   const error = ref<string | null>(null)
@@ -188,6 +202,17 @@ export const useDockerStore = defineStore('docker', () => {
     }
   }
 
+  async function loadResourceLimits(id: string) {
+    if (DEMO_MODE || !isConnected.value) return
+
+    try {
+      const limits = await invoke<ResourceLimits>('get_container_resource_limits', { id })
+      resourceLimits.value.set(id, limits)
+    } catch (e) {
+      error.value = String(e)
+    }
+  }
+
   async function loadAllStats() {
     const running = containers.value.filter((c) => c.state === 'running')
     await Promise.all(running.map((c) => loadContainerStats(c.id)))
@@ -258,6 +283,7 @@ export const useDockerStore = defineStore('docker', () => {
     containers,
     dockerInfo,
     containerStats,
+    resourceLimits,
     isConnected,
     loading,
     error,
@@ -270,6 +296,7 @@ export const useDockerStore = defineStore('docker', () => {
     loadContainers,
     loadDockerInfo,
     loadContainerStats,
+    loadResourceLimits,
     loadAllStats,
     startContainer,
     stopContainer,
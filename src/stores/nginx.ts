@@ -11,12 +11,117 @@ export interface NginxVhost {
   ssl_cert_path: string | null
   ssl_key_path: string | null
   config_path: string
+  enabled: boolean
+  rate_limit: RateLimitConfig | null
+  websocket_enabled: boolean
+  websocket_path: string
+  security_headers: SecurityHeadersConfig | null
+  compression: CompressionConfig | null
+  basic_auth: BasicAuthConfig | null
+  ip_restrictions: IpRestriction[]
+  upstreams: UpstreamConfig[]
+  client_max_body_size: string | null
+  fastcgi: FastcgiConfig | null
+  redirects: RedirectRule[]
+  cors: CorsConfig | null
+  fpm_upstream: string | null
+  fpm_pool: string | null
+  rewrites: RewriteRule[]
+  error_pages: Record<number, string>
+  includes: string[]
+}
+
+export interface NginxInclude {
+  id: string
+  name: string
+  filename: string
+  content: string
+}
+
+export type RewriteFlag = 'last' | 'break' | 'redirect' | 'permanent'
+
+export interface RewriteRule {
+  pattern: string
+  replacement: string
+  flag: RewriteFlag
+}
+
+export interface CorsConfig {
+  allowed_origins: string[]
+  allowed_methods: string[]
+  allowed_headers: string[]
+  allow_credentials: boolean
+  max_age: number
+}
+
+export interface RedirectRule {
+  source_path: string
+  dest_url: string
+  permanent: boolean
+}
+
+export interface FastcgiConfig {
+  fastcgi_buffer_size: string | null
+  fastcgi_buffers: string | null
+}
+
+export type LoadBalancingMethod = 'round_robin' | 'least_conn' | 'ip_hash'
+
+export interface UpstreamServer {
+  address: string
+  weight: number | null
+  max_fails: number | null
+}
+
+export interface UpstreamConfig {
+  name: string
+  servers: UpstreamServer[]
+  balancing: LoadBalancingMethod
+}
+
+export interface IpRestriction {
+  path: string
+  allowed_ips: string[]
+  denied_ips: string[]
+}
+
+export interface BasicAuthConfig {
+  realm: string
+  credentials: [string, string][]
+}
+
+export interface CompressionConfig {
+  gzip_enabled: boolean
+  gzip_min_length: number
+  gzip_types: string[]
+  brotli_enabled: boolean
+}
+
+export interface SecurityHeadersConfig {
+  hsts_max_age: number | null
+  csp: string | null
+  x_frame_options: string
+  x_content_type_options: boolean
+  referrer_policy: string
+}
+
+export interface RateLimitConfig {
+  requests_per_second: number
+  burst: number
+  zone_name: string
+}
+
+export interface NginxLintWarning {
+  line: number
+  severity: string
+  message: string
 }
 
 export interface NginxTestResult {
   success: boolean
   output: string
   errors: string[]
+  warnings: string[]
 }
 
 export const useNginxStore = defineStore('nginx', () => {
@@ -24,6 +129,7 @@ export const useNginxStore = defineStore('nginx', () => {
   const loading = ref(false)
   const error = ref<string | null>(null)
   const testResult = ref<NginxTestResult | null>(null)
+  const includes = ref<NginxInclude[]>([])
 
   async function loadVhosts() {
     try {
@@ -43,7 +149,8 @@ export const useNginxStore = defineStore('nginx', () => {
     phpEnabled: boolean,
     sslEnabled: boolean,
     sslCertPath?: string,
-    sslKeyPath?: string
+    sslKeyPath?: string,
+    redirects?: RedirectRule[]
   ) {
     try {
       loading.value = true
@@ -54,7 +161,8 @@ export const useNginxStore = defineStore('nginx', () => {
         phpEnabled,
         sslEnabled,
         sslCertPath: sslCertPath || null,
-        sslKeyPath: sslKeyPath || null
+        sslKeyPath: sslKeyPath || null,
+        redirects: redirects ?? null
       })
       vhosts.value.push(vhost)
       return vhost
@@ -98,6 +206,59 @@ export const useNginxStore = defineStore('nginx', () => {
     }
   }
 
+  async function cloneVhost(sourceId: string, newServerName: string) {
+    try {
+      loading.value = true
+      error.value = null
+      const vhost = await invoke<NginxVhost>('clone_vhost', { sourceId, newServerName })
+      vhosts.value.push(vhost)
+      return vhost
+    } catch (e) {
+      error.value = String(e)
+      throw e
+    } finally {
+      loading.value = false
+    }
+  }
+
+  async function setVhostEnabled(id: string, enabled: boolean) {
+    try {
+      loading.value = true
+      error.value = null
+      const updated = await invoke<NginxVhost>('set_vhost_enabled', { id, enabled })
+      const idx = vhosts.value.findIndex(v => v.id === updated.id)
+      if (idx !== -1) {
+        vhosts.value[idx] = updated
+      }
+      return updated
+    } catch (e) {
+      error.value = String(e)
+      throw e
+    } finally {
+      loading.value = false
+    }
+  }
+
+  async function setVhostRateLimit(id: string, config: RateLimitConfig | null) {
+    try {
+      loading.value = true
+      error.value = null
+      const updated = config
+        ? await invoke<NginxVhost>('set_vhost_rate_limit', { id, config })
+        : await invoke<NginxVhost>('remove_vhost_rate_limit', { id })
+      const idx = vhosts.value.findIndex(v => v.id === updated.id)
+      if (idx !== -1) {
+        vhosts.value[idx] = updated
+      }
+      return updated
+    } catch (e) {
+      error.value = String(e)
+      throw e
+    } finally {
+      loading.value = false
+    }
+  }
+
   async function getVhostConfig(id: string): Promise<string> {
     try {
       return await invoke<string>('get_vhost_config', { id })
@@ -107,11 +268,12 @@ export const useNginxStore = defineStore('nginx', () => {
     }
   }
 
-  async function saveVhostConfig(id: string, content: string) {
+  // The config is saved either way; the lint warnings are advisory
+  async function saveVhostConfig(id: string, content: string): Promise<NginxLintWarning[]> {
     try {
       loading.value = true
       error.value = null
-      await invoke('save_vhost_config', { id, content })
+      return await invoke<NginxLintWarning[]>('save_vhost_config', { id, content })
     } catch (e) {
       error.value = String(e)
       throw e
@@ -156,19 +318,86 @@ export const useNginxStore = defineStore('nginx', () => {
     }
   }
 
+  async function loadIncludes() {
+    try {
+      error.value = null
+      includes.value = await invoke<NginxInclude[]>('list_nginx_includes')
+    } catch (e) {
+      error.value = String(e)
+    }
+  }
+
+  async function createInclude(name: string, filename: string, content: string) {
+    try {
+      error.value = null
+      const include = await invoke<NginxInclude>('create_nginx_include', { name, filename, content })
+      includes.value.push(include)
+      return include
+    } catch (e) {
+      error.value = String(e)
+      throw e
+    }
+  }
+
+  async function updateInclude(include: NginxInclude) {
+    try {
+      error.value = null
+      const updated = await invoke<NginxInclude>('update_nginx_include', { include })
+      const idx = includes.value.findIndex(i => i.id === updated.id)
+      if (idx !== -1) {
+        includes.value[idx] = updated
+      }
+      // Renames are propagated to the vhosts that use the include
+      await loadVhosts()
+      return updated
+    } catch (e) {
+      error.value = String(e)
+      throw e
+    }
+  }
+
+  async function deleteInclude(id: string) {
+    try {
+      error.value = null
+      await invoke('delete_nginx_include', { id })
+      includes.value = includes.value.filter(i => i.id !== id)
+    } catch (e) {
+      error.value = String(e)
+      throw e
+    }
+  }
+
+  async function getDefaultErrorPages(documentRoot: string): Promise<Record<number, string>> {
+    try {
+      return await invoke<Record<number, string>>('generate_default_error_pages', { documentRoot })
+    } catch (e) {
+      error.value = String(e)
+      return {}
+    }
+  }
+
   return {
     vhosts,
     loading,
     error,
     testResult,
+    includes,
     loadVhosts,
     createVhost,
     updateVhost,
     deleteVhost,
+    cloneVhost,
+    setVhostEnabled,
+    setVhostRateLimit,
     getVhostConfig,
     saveVhostConfig,
     testConfig,
     reloadNginx,
-    getDefaultConfig
+    getDefaultConfig,
+    getDefaultErrorPages,
+    loadIncludes,
+    createInclude,
+    updateInclude,
+    deleteInclude
   }
 })
@@ -33,6 +33,34 @@ export interface Project {
   updated_at: number
 }
 
+export interface DiffLine {
+  kind: 'added' | 'removed' | 'unchanged'
+  content: string
+  old_line: number | null
+  new_line: number | null
+}
+
+export interface FileDiff {
+  path: string
+  additions: number
+  deletions: number
+  lines: DiffLine[]
+}
+
+// With a conflict the compose file has manual edits and nothing was saved
+export interface ProjectUpdate {
+  project: Project
+  conflict: FileDiff | null
+}
+
+export type ProjectTemplate = 'lamp' | 'lemp' | 'laravel' | 'wordpress' | 'nodeexpress' | 'custom'
+
+export interface TemplateInfo {
+  name: ProjectTemplate
+  description: string
+  services: ServiceConfig[]
+}
+
 export interface DirectoryEntry {
   name: string
   path: string
@@ -75,11 +103,11 @@ export const useProjectStore = defineStore('project', () => {
     }
   }
 
-  async function createProject(name: string, rootPath: string) {
+  async function createProject(name: string, rootPath: string, template?: ProjectTemplate) {
     try {
       loading.value = true
       error.value = null
-      const project = await invoke<Project>('create_project', { name, rootPath })
+      const project = await invoke<Project>('create_project', { name, rootPath, template: template ?? null })
       projects.value.push(project)
       return project
     } catch (e) {
@@ -90,16 +118,18 @@ export const useProjectStore = defineStore('project', () => {
     }
   }
 
-  async function updateProject(project: Project) {
+  async function updateProject(project: Project, overwriteCompose = false) {
     try {
       loading.value = true
       error.value = null
-      const updated = await invoke<Project>('update_project', { project })
-      const idx = projects.value.findIndex(p => p.id === updated.id)
-      if (idx !== -1) {
-        projects.value[idx] = updated
+      const result = await invoke<ProjectUpdate>('update_project', { project, overwriteCompose })
+      if (!result.conflict) {
+        const idx = projects.value.findIndex(p => p.id === result.project.id)
+        if (idx !== -1) {
+          projects.value[idx] = result.project
+        }
       }
-      return updated
+      return result
     } catch (e) {
       error.value = String(e)
       throw e
@@ -126,7 +156,7 @@ export const useProjectStore = defineStore('project', () => {
     try {
       loading.value = true
       error.value = null
-      return await invoke<string>('compose_up', { projectId })
+      await invoke('compose_up', { projectId })
     } catch (e) {
       error.value = String(e)
       throw e
@@ -135,11 +165,11 @@ export const useProjectStore = defineStore('project', () => {
     }
   }
 
-  async function composeDown(projectId: string) {
+  async function composeDown(projectId: string, removeVolumes = false) {
     try {
       loading.value = true
       error.value = null
-      return await invoke<string>('compose_down', { projectId })
+      await invoke('compose_down', { projectId, removeVolumes })
     } catch (e) {
       error.value = String(e)
       throw e
@@ -152,7 +182,7 @@ export const useProjectStore = defineStore('project', () => {
     try {
       loading.value = true
       error.value = null
-      return await invoke<string>('compose_restart', { projectId })
+      await invoke('compose_restart', { projectId })
     } catch (e) {
       error.value = String(e)
       throw e
@@ -170,6 +200,15 @@ export const useProjectStore = defineStore('project', () => {
     }
   }
 
+  async function listProjectTemplates(): Promise<TemplateInfo[]> {
+    try {
+      return await invoke<TemplateInfo[]>('list_project_templates')
+    } catch (e) {
+      error.value = String(e)
+      return []
+    }
+  }
+
   async function getHomeDir(): Promise<string> {
     try {
       return await invoke<string>('get_home_dir')
@@ -193,6 +232,7 @@ export const useProjectStore = defineStore('project', () => {
     composeDown,
     composeRestart,
     listDirectory,
+    listProjectTemplates,
     getHomeDir
   }
 })
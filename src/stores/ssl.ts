@@ -15,6 +15,7 @@ export interface Certificate {
   key_path: string
   created_at: number
   is_wildcard: boolean
+  expires_at: number | null
 }
 
 export const useSslStore = defineStore('ssl', () => {
@@ -77,6 +78,15 @@ export const useSslStore = defineStore('ssl', () => {
     }
   }
 
+  async function getExpiringCertificates(withinDays: number): Promise<Certificate[]> {
+    try {
+      return await invoke<Certificate[]>('get_expiring_certificates', { withinDays })
+    } catch (e) {
+      error.value = String(e)
+      return []
+    }
+  }
+
   async function deleteCertificate(domain: string) {
     try {
       loading.value = true
@@ -109,6 +119,7 @@ export const useSslStore = defineStore('ssl', () => {
     installCA,
     loadCertificates,
     generateCertificate,
+    getExpiringCertificates,
     deleteCertificate,
     getInstallInstructions
   }